@@ -1,60 +1,489 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 
+/// Redirect the shopper to `url`, using `method` ("GET" or "POST" with `data` as form fields).
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "PascalCase")]
-pub struct SchemeRedirectData {
-    m_d: String,
+#[serde(rename_all = "camelCase")]
+pub struct RedirectAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    pub url: String,
+
+    pub method: String,
+
+    /// The form fields to post when `method` is "POST", e.g. `MD`, `PaReq` and `TermUrl` for
+    /// 3D Secure 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub data: Option<Map<String, Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The step of a native 3D Secure 2 authentication.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreeDS2Subtype {
+    /// Collect the shopper's device fingerprint.
+    Fingerprint,
+
+    /// Present the issuer's challenge to the shopper.
+    Challenge,
+}
+
+/// Perform native 3D Secure 2 authentication.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreeDS2Action {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    pub subtype: ThreeDS2Subtype,
+
+    pub token: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub authorisation_token: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Show a QR code the shopper scans to complete the payment, e.g. for Swish.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QrCodeAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    pub qr_code_data: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Wait while the shopper completes the payment in another app, e.g. MB WAY or BLIK.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AwaitAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Show a voucher the shopper pays offline, e.g. Boleto or Multibanco.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VoucherAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reference: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expires_at: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub download_url: Option<String>,
 
-    pa_req: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub instructions_url: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    term_url: Option<String>,
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
 }
 
+/// Hand over to a payment method's own SDK, e.g. WeChat Pay or PayPal.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SdkAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub sdk_data: Option<Map<String, Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Redirect the shopper to a payment method's app, handled natively by the client.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeRedirectAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method_type: Option<String>,
+
+    pub url: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub method: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub native_redirect_data: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_data: Option<String>,
+
+    /// Fields not covered above, kept so the action can be passed on unchanged.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An action the shopper must complete before the payment can be finalised. Serializing an
+/// action gives back the object Adyen returned, so it can be passed unchanged to Adyen Web's
+/// `handleAction`.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#responses-200-action
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
 #[serde(tag = "type")]
-pub enum Scheme {
+pub enum Action {
     #[serde(rename = "redirect")]
-    #[serde(rename_all = "camelCase")]
-    Redirect {
-        url: String,
-        data: SchemeRedirectData,
-        method: String,
-    },
+    Redirect(RedirectAction),
 
     #[serde(rename = "threeDS2")]
-    #[serde(rename_all = "camelCase")]
-    ThreeDS2 {
-        payment_data: String,
-        authorisation_token: String,
-        subtype: String,
-        token: String,
-    },
+    ThreeDS2(ThreeDS2Action),
+
+    #[serde(rename = "qrCode")]
+    QrCode(QrCodeAction),
+
+    #[serde(rename = "await")]
+    Await(AwaitAction),
+
+    #[serde(rename = "voucher")]
+    Voucher(VoucherAction),
+
+    #[serde(rename = "sdk")]
+    Sdk(SdkAction),
+
+    #[serde(rename = "nativeRedirect")]
+    NativeRedirect(NativeRedirectAction),
+
+    /// An action type this crate does not know, or whose fields could not be parsed.
+    #[serde(untagged)]
+    Unknown(Value),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(tag = "paymentMethodType")]
-#[serde(rename_all = "lowercase")]
-pub enum Action {
-    #[serde(rename_all = "camelCase")]
-    Scheme(Scheme),
-
-    #[serde(rename_all = "camelCase")]
-    Swish {
-        qr_code_data: String,
-        r#type: String,
-        payment_data: String,
-        url: String,
-    },
-
-    #[serde(rename_all = "camelCase")]
-    Vipps {
-        method: String,
-        url: String,
-        r#type: String,
-    },
-
-    #[serde(rename_all = "camelCase")]
-    GooglePay(Scheme),
+impl Action {
+    /// The payment method the action belongs to, e.g. "scheme" or "swish".
+    pub fn payment_method_type(&self) -> Option<&str> {
+        match self {
+            Action::Redirect(action) => action.payment_method_type.as_deref(),
+            Action::ThreeDS2(action) => action.payment_method_type.as_deref(),
+            Action::QrCode(action) => action.payment_method_type.as_deref(),
+            Action::Await(action) => action.payment_method_type.as_deref(),
+            Action::Voucher(action) => action.payment_method_type.as_deref(),
+            Action::Sdk(action) => action.payment_method_type.as_deref(),
+            Action::NativeRedirect(action) => action.payment_method_type.as_deref(),
+            Action::Unknown(value) => value.get("paymentMethodType").and_then(Value::as_str),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D>(deserializer: D) -> Result<Action, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Fields that are null are kept in `other` rather than parsed, so that they are
+        // serialized again instead of being skipped as `None`.
+        fn parse<T: DeserializeOwned>(
+            fields: Map<String, Value>,
+            other: fn(&mut T) -> &mut Map<String, Value>,
+        ) -> Option<T> {
+            let (nulls, fields): (Map<String, Value>, Map<String, Value>) =
+                fields.into_iter().partition(|(_, value)| value.is_null());
+
+            let mut action: T = serde_json::from_value(Value::Object(fields)).ok()?;
+            other(&mut action).extend(nulls);
+            Some(action)
+        }
+
+        let value = Value::deserialize(deserializer)?;
+
+        let mut fields = match &value {
+            Value::Object(fields) => fields.clone(),
+            _ => return Ok(Action::Unknown(value)),
+        };
+
+        // The tag is removed so it does not end up among the other fields.
+        let action = match fields.remove("type") {
+            Some(Value::String(r#type)) => match r#type.as_str() {
+                "redirect" => parse(fields, |action: &mut RedirectAction| &mut action.other)
+                    .map(Action::Redirect),
+                "threeDS2" => parse(fields, |action: &mut ThreeDS2Action| &mut action.other)
+                    .map(Action::ThreeDS2),
+                "qrCode" => {
+                    parse(fields, |action: &mut QrCodeAction| &mut action.other).map(Action::QrCode)
+                }
+                "await" => {
+                    parse(fields, |action: &mut AwaitAction| &mut action.other).map(Action::Await)
+                }
+                "voucher" => parse(fields, |action: &mut VoucherAction| &mut action.other)
+                    .map(Action::Voucher),
+                "sdk" => parse(fields, |action: &mut SdkAction| &mut action.other).map(Action::Sdk),
+                "nativeRedirect" => parse(fields, |action: &mut NativeRedirectAction| {
+                    &mut action.other
+                })
+                .map(Action::NativeRedirect),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        Ok(action.unwrap_or(Action::Unknown(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(g: &str) -> Action {
+        serde_json::from_str(g).unwrap()
+    }
+
+    /// Asserts that `g` serializes back to the same JSON, compared in compact form with sorted
+    /// keys.
+    fn assert_round_trip(g: &str) {
+        let expected = serde_json::to_string(&serde_json::from_str::<Value>(g).unwrap()).unwrap();
+        let action = serde_json::to_value(parse(g)).unwrap();
+        assert_eq!(serde_json::to_string(&action).unwrap(), expected);
+    }
+
+    const REDIRECT: &str = r#"{
+        "paymentMethodType": "scheme",
+        "url": "https://checkoutshopper-test.adyen.com/checkoutshopper/threeDS/redirect",
+        "method": "POST",
+        "data": { "MD": "md", "PaReq": "pa-req", "TermUrl": "https://example.com/return" },
+        "type": "redirect"
+    }"#;
+
+    const THREE_D_S2: &str = r#"{
+        "paymentMethodType": "scheme",
+        "subtype": "fingerprint",
+        "token": "eyJ0aHJlZURTTWV0aG9kTm90aWZpY2F0aW9uVVJMIjoi",
+        "authorisationToken": "Ab02b4c0!",
+        "paymentData": "Ab02b4c0!BQABAgCW",
+        "type": "threeDS2"
+    }"#;
+
+    const QR_CODE: &str = r#"{
+        "paymentMethodType": "swish",
+        "qrCodeData": "D/Dv8mx8FNiNBb",
+        "url": "https://test.adyen.com/hpp/swish.shtml",
+        "paymentData": "Ab02b4c0!BQABAgA",
+        "type": "qrCode"
+    }"#;
+
+    const AWAIT: &str = r#"{
+        "paymentMethodType": "mbway",
+        "paymentData": "Ab02b4c0!BQABAgB",
+        "type": "await"
+    }"#;
+
+    const VOUCHER: &str = r#"{
+        "paymentMethodType": "multibanco",
+        "reference": "501 422 944",
+        "expiresAt": "2026-10-25T12:00:00",
+        "downloadUrl": "https://test.adyen.com/hpp/generationVoucher.shtml",
+        "initialAmount": { "currency": "EUR", "value": 1000 },
+        "entity": "11249",
+        "type": "voucher"
+    }"#;
+
+    const SDK: &str = r#"{
+        "paymentMethodType": "wechatpaySDK",
+        "sdkData": { "appid": "wx", "noncestr": "nonce" },
+        "paymentData": "Ab02b4c0!BQABAgC",
+        "type": "sdk"
+    }"#;
+
+    const NATIVE_REDIRECT: &str = r#"{
+        "paymentMethodType": "ideal",
+        "url": "https://test.adyen.com/hpp/nativeRedirect.shtml",
+        "method": "GET",
+        "nativeRedirectData": "Ab02b4c0!BQABAgD",
+        "type": "nativeRedirect"
+    }"#;
+
+    #[test]
+    fn parses_each_type() {
+        match parse(REDIRECT) {
+            Action::Redirect(action) => {
+                assert_eq!(action.method, "POST");
+                assert_eq!(action.data.unwrap()["MD"], "md");
+            }
+            action => panic!("{:?}", action),
+        }
+        match parse(THREE_D_S2) {
+            Action::ThreeDS2(action) => {
+                assert_eq!(action.subtype, ThreeDS2Subtype::Fingerprint);
+                assert_eq!(action.authorisation_token.as_deref(), Some("Ab02b4c0!"));
+            }
+            action => panic!("{:?}", action),
+        }
+        match parse(QR_CODE) {
+            Action::QrCode(action) => assert_eq!(action.qr_code_data, "D/Dv8mx8FNiNBb"),
+            action => panic!("{:?}", action),
+        }
+        match parse(AWAIT) {
+            Action::Await(action) => {
+                assert_eq!(action.payment_data.as_deref(), Some("Ab02b4c0!BQABAgB"))
+            }
+            action => panic!("{:?}", action),
+        }
+        match parse(VOUCHER) {
+            Action::Voucher(action) => {
+                assert_eq!(action.reference.as_deref(), Some("501 422 944"));
+                // Fields without a typed counterpart are kept.
+                assert_eq!(action.other["entity"], "11249");
+                assert!(!action.other.contains_key("type"));
+            }
+            action => panic!("{:?}", action),
+        }
+        match parse(SDK) {
+            Action::Sdk(action) => assert_eq!(action.sdk_data.unwrap()["appid"], "wx"),
+            action => panic!("{:?}", action),
+        }
+        match parse(NATIVE_REDIRECT) {
+            Action::NativeRedirect(action) => {
+                assert_eq!(action.method.as_deref(), Some("GET"));
+                assert_eq!(
+                    action.native_redirect_data.as_deref(),
+                    Some("Ab02b4c0!BQABAgD")
+                );
+            }
+            action => panic!("{:?}", action),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_unknown() {
+        for g in [
+            // An unknown type.
+            r#"{"paymentMethodType": "bankTransfer", "totalAmount": 1, "type": "bankTransfer"}"#,
+            // A known type without a required field.
+            r#"{"paymentMethodType": "scheme", "method": "GET", "type": "redirect"}"#,
+            // A known type with a field of the wrong type.
+            r#"{"qrCodeData": 1, "type": "qrCode"}"#,
+            // No type.
+            r#"{"url": "https://example.com"}"#,
+        ] {
+            let action = parse(g);
+            assert!(matches!(action, Action::Unknown(_)), "{:?}", action);
+            assert_round_trip(g);
+        }
+
+        assert_eq!(
+            parse(r#"{"paymentMethodType": "bankTransfer", "type": "bankTransfer"}"#)
+                .payment_method_type(),
+            Some("bankTransfer")
+        );
+    }
+
+    #[test]
+    fn round_trips_each_type() {
+        for g in [
+            REDIRECT,
+            THREE_D_S2,
+            QR_CODE,
+            AWAIT,
+            VOUCHER,
+            SDK,
+            NATIVE_REDIRECT,
+        ] {
+            assert_round_trip(g);
+        }
+    }
+
+    #[test]
+    fn round_trips_null_fields() {
+        let g = r#"{
+            "paymentMethodType": "scheme",
+            "url": "https://example.com/redirect",
+            "method": "GET",
+            "data": null,
+            "paymentData": null,
+            "type": "redirect"
+        }"#;
+        match parse(g) {
+            Action::Redirect(action) => {
+                assert_eq!(action.data, None);
+                assert_eq!(action.payment_data, None);
+            }
+            action => panic!("{:?}", action),
+        }
+        assert_round_trip(g);
+    }
 }
@@ -1,9 +1,430 @@
-use crate::Currency;
+use crate::{error::Error, Currency};
+use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// How to round a decimal amount that has more decimals than the currency's minor unit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rounding {
+    /// Do not round. Converting a value that cannot be represented exactly in minor units is an
+    /// error.
+    Exact,
+
+    /// Round to the nearest minor unit, with halves rounded away from zero (12.345 -> 12.35).
+    HalfUp,
+
+    /// Round to the nearest minor unit, with halves rounded to the nearest even number
+    /// (12.345 -> 12.34). Also known as banker's rounding.
+    HalfEven,
+
+    /// Round towards zero (12.349 -> 12.34).
+    Down,
+
+    /// Round away from zero (12.341 -> 12.35).
+    Up,
+}
+
+/// An amount of money in the minor units of a currency, e.g. 1050 NOK is 10.50 kr.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Amount {
     pub value: u64,
     pub currency: Currency,
 }
+
+impl Amount {
+    pub fn new(value: u64, currency: Currency) -> Amount {
+        Amount { value, currency }
+    }
+
+    /// A zero amount in the given currency.
+    pub fn zero(currency: Currency) -> Amount {
+        Amount { value: 0, currency }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Converts a decimal amount in major units (e.g. 12.34 NOK) into minor units, rounding as
+    /// requested. Returns an error for negative amounts, amounts that overflow, and amounts that
+    /// need rounding when `rounding` is `Rounding::Exact`.
+    pub fn from_decimal(
+        amount: &Decimal,
+        currency: Currency,
+        rounding: Rounding,
+    ) -> Result<Amount, Error> {
+        let exponent = currency.exponent();
+
+        let rounded = match rounding {
+            Rounding::Exact => *amount,
+            Rounding::HalfUp => {
+                amount.round_dp_with_strategy(exponent, RoundingStrategy::MidpointAwayFromZero)
+            }
+            Rounding::HalfEven => {
+                amount.round_dp_with_strategy(exponent, RoundingStrategy::MidpointNearestEven)
+            }
+            Rounding::Down => amount.round_dp_with_strategy(exponent, RoundingStrategy::ToZero),
+            Rounding::Up => amount.round_dp_with_strategy(exponent, RoundingStrategy::AwayFromZero),
+        };
+
+        // Get minor units from decimal.
+        let minor_units = Decimal::from(10u64.pow(exponent))
+            .checked_mul(rounded)
+            .ok_or_else(|| Error::ConversionError(format!("\"{}\" is out of range", amount)))?;
+
+        if minor_units.fract() != Decimal::ZERO {
+            return Err(Error::ConversionError(format!(
+                "\"{}\" has more than {} decimals for {}",
+                amount, exponent, currency
+            )));
+        }
+
+        match minor_units.to_u64() {
+            Some(value) => Ok(Amount { value, currency }),
+            None => Err(Error::ConversionError(format!(
+                "could not convert \"{}\" to u64",
+                amount
+            ))),
+        }
+    }
+
+    /// Converts the amount into a decimal in major units, e.g. 1234 NOK becomes 12.34.
+    pub fn to_decimal(&self) -> Decimal {
+        Decimal::from_i128_with_scale(i128::from(self.value), self.currency.exponent())
+    }
+
+    /// Adds two amounts. Returns an error if the currencies differ or the result overflows.
+    pub fn checked_add(&self, other: &Amount) -> Result<Amount, Error> {
+        self.ensure_same_currency(other)?;
+
+        match self.value.checked_add(other.value) {
+            Some(value) => Ok(Amount::new(value, self.currency)),
+            None => Err(Error::ConversionError(format!(
+                "{} + {} overflows",
+                self.value, other.value
+            ))),
+        }
+    }
+
+    /// Subtracts `other` from the amount. Returns an error if the currencies differ or the result
+    /// would be negative.
+    pub fn checked_sub(&self, other: &Amount) -> Result<Amount, Error> {
+        self.ensure_same_currency(other)?;
+
+        match self.value.checked_sub(other.value) {
+            Some(value) => Ok(Amount::new(value, self.currency)),
+            None => Err(Error::ConversionError(format!(
+                "{} - {} is negative",
+                self.value, other.value
+            ))),
+        }
+    }
+
+    /// Multiplies the amount by a quantity. Returns an error if the result overflows.
+    pub fn checked_mul(&self, quantity: u64) -> Result<Amount, Error> {
+        match self.value.checked_mul(quantity) {
+            Some(value) => Ok(Amount::new(value, self.currency)),
+            None => Err(Error::ConversionError(format!(
+                "{} * {} overflows",
+                self.value, quantity
+            ))),
+        }
+    }
+
+    /// Multiplies the amount by a decimal factor, e.g. a tax rate, rounding the result to minor
+    /// units as requested.
+    pub fn checked_mul_decimal(
+        &self,
+        factor: &Decimal,
+        rounding: Rounding,
+    ) -> Result<Amount, Error> {
+        match self.to_decimal().checked_mul(*factor) {
+            Some(product) => Amount::from_decimal(&product, self.currency, rounding),
+            None => Err(Error::ConversionError(format!(
+                "{} * {} overflows",
+                self.to_decimal(),
+                factor
+            ))),
+        }
+    }
+
+    /// Splits the amount according to `ratios` without losing minor units. Units left over after
+    /// the proportional split are handed out one at a time, starting with the first share, so the
+    /// shares always add up to the original amount.
+    pub fn allocate(&self, ratios: &[u64]) -> Result<Vec<Amount>, Error> {
+        let total: u128 = ratios.iter().map(|ratio| u128::from(*ratio)).sum();
+        if total == 0 {
+            return Err(Error::ConversionError(String::from(
+                "cannot allocate an amount without a non-zero ratio",
+            )));
+        }
+
+        let value = u128::from(self.value);
+        let mut shares: Vec<u64> = ratios
+            .iter()
+            // Each share is at most `self.value`, so the cast cannot truncate.
+            .map(|ratio| (value * u128::from(*ratio) / total) as u64)
+            .collect();
+
+        let mut remainder = self.value - shares.iter().sum::<u64>();
+        for (share, ratio) in shares.iter_mut().zip(ratios) {
+            if remainder == 0 {
+                break;
+            }
+            if *ratio > 0 {
+                *share += 1;
+                remainder -= 1;
+            }
+        }
+
+        Ok(shares
+            .into_iter()
+            .map(|value| Amount::new(value, self.currency))
+            .collect())
+    }
+
+    /// Splits the amount into `parts` shares that differ by at most one minor unit.
+    pub fn split(&self, parts: usize) -> Result<Vec<Amount>, Error> {
+        self.allocate(&vec![1; parts])
+    }
+
+    fn ensure_same_currency(&self, other: &Amount) -> Result<(), Error> {
+        if self.currency != other.currency {
+            return Err(Error::CurrencyMismatch {
+                expected: self.currency,
+                found: other.currency,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn decimal(g: &str) -> Decimal {
+        Decimal::from_str(g).unwrap()
+    }
+
+    fn minor_units(g: &str, currency: Currency, rounding: Rounding) -> Option<u64> {
+        Amount::from_decimal(&decimal(g), currency, rounding)
+            .ok()
+            .map(|amount| amount.value)
+    }
+
+    #[test]
+    fn rounds_half_way_values() {
+        let cases = [
+            ("12.345", Rounding::Exact, None),
+            ("12.345", Rounding::HalfUp, Some(1235)),
+            ("12.345", Rounding::HalfEven, Some(1234)),
+            ("12.355", Rounding::HalfEven, Some(1236)),
+            ("12.345", Rounding::Down, Some(1234)),
+            ("12.345", Rounding::Up, Some(1235)),
+            ("12.341", Rounding::Up, Some(1235)),
+            ("12.349", Rounding::Down, Some(1234)),
+            ("12.34", Rounding::Exact, Some(1234)),
+        ];
+        for (g, rounding, expected) in cases {
+            assert_eq!(
+                minor_units(g, Currency::NOK, rounding),
+                expected,
+                "{} {:?}",
+                g,
+                rounding
+            );
+        }
+    }
+
+    #[test]
+    fn zero_exponent_currency() {
+        assert_eq!(
+            minor_units("1234", Currency::JPY, Rounding::Exact),
+            Some(1234)
+        );
+        assert_eq!(minor_units("1234.5", Currency::JPY, Rounding::Exact), None);
+        assert_eq!(
+            minor_units("1234.5", Currency::JPY, Rounding::HalfUp),
+            Some(1235)
+        );
+        assert_eq!(
+            minor_units("1234.5", Currency::JPY, Rounding::HalfEven),
+            Some(1234)
+        );
+        assert_eq!(
+            minor_units("1235.5", Currency::JPY, Rounding::HalfEven),
+            Some(1236)
+        );
+        assert_eq!(
+            Amount::new(1234, Currency::JPY).to_decimal(),
+            decimal("1234")
+        );
+    }
+
+    #[test]
+    fn three_decimal_currency() {
+        assert_eq!(
+            minor_units("1.234", Currency::KWD, Rounding::Exact),
+            Some(1234)
+        );
+        assert_eq!(minor_units("1.2345", Currency::KWD, Rounding::Exact), None);
+        assert_eq!(
+            minor_units("1.2345", Currency::KWD, Rounding::HalfUp),
+            Some(1235)
+        );
+        assert_eq!(
+            minor_units("1.2345", Currency::KWD, Rounding::HalfEven),
+            Some(1234)
+        );
+        assert_eq!(
+            Amount::new(1234, Currency::KWD).to_decimal(),
+            decimal("1.234")
+        );
+    }
+
+    #[test]
+    fn rejects_negative_and_out_of_range_decimals() {
+        assert_eq!(minor_units("-1.00", Currency::NOK, Rounding::Exact), None);
+        assert_eq!(
+            minor_units("184467440737095516.16", Currency::NOK, Rounding::Exact),
+            None
+        );
+        assert_eq!(
+            minor_units("184467440737095516.15", Currency::NOK, Rounding::Exact),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        for currency in [Currency::NOK, Currency::JPY, Currency::KWD] {
+            let amount = Amount::new(123456, currency);
+            assert_eq!(
+                Amount::from_decimal(&amount.to_decimal(), currency, Rounding::Exact).unwrap(),
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn convert_decimal_into_minor_units_does_not_truncate() {
+        assert_eq!(
+            crate::convert_decimal_into_minor_units(&decimal("12.34"), &Currency::NOK).unwrap(),
+            1234
+        );
+        assert!(
+            crate::convert_decimal_into_minor_units(&decimal("12.349"), &Currency::NOK).is_err()
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let nok = |value| Amount::new(value, Currency::NOK);
+
+        assert_eq!(nok(100).checked_add(&nok(50)).unwrap(), nok(150));
+        assert_eq!(nok(100).checked_sub(&nok(50)).unwrap(), nok(50));
+        assert_eq!(nok(100).checked_mul(3).unwrap(), nok(300));
+        assert_eq!(
+            nok(1000)
+                .checked_mul_decimal(&decimal("0.125"), Rounding::HalfUp)
+                .unwrap(),
+            nok(125)
+        );
+        assert_eq!(
+            nok(1001)
+                .checked_mul_decimal(&decimal("0.25"), Rounding::HalfEven)
+                .unwrap(),
+            nok(250)
+        );
+        assert!(nok(1001)
+            .checked_mul_decimal(&decimal("0.25"), Rounding::Exact)
+            .is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic_rejects_overflow() {
+        let nok = |value| Amount::new(value, Currency::NOK);
+
+        assert!(nok(u64::MAX).checked_add(&nok(1)).is_err());
+        assert!(nok(1).checked_sub(&nok(2)).is_err());
+        assert!(nok(u64::MAX).checked_mul(2).is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic_rejects_currency_mismatch() {
+        let nok = Amount::new(100, Currency::NOK);
+        let sek = Amount::new(100, Currency::SEK);
+
+        for result in [nok.checked_add(&sek), nok.checked_sub(&sek)] {
+            assert!(matches!(
+                result,
+                Err(Error::CurrencyMismatch {
+                    expected: Currency::NOK,
+                    found: Currency::SEK,
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn allocate_adds_up() {
+        let ratios: &[&[u64]] = &[&[1, 1, 1], &[70, 20, 10], &[3, 0, 7], &[1], &[u64::MAX, 1]];
+        for value in [0, 1, 2, 100, 101, 9_999, u64::MAX] {
+            let amount = Amount::new(value, Currency::NOK);
+            for ratios in ratios {
+                let shares = amount.allocate(ratios).unwrap();
+                assert_eq!(shares.len(), ratios.len());
+                assert_eq!(
+                    shares
+                        .iter()
+                        .map(|share| u128::from(share.value))
+                        .sum::<u128>(),
+                    u128::from(value),
+                    "{} {:?}",
+                    value,
+                    ratios
+                );
+                for (share, ratio) in shares.iter().zip(ratios.iter()) {
+                    assert_eq!(share.currency, Currency::NOK);
+                    if *ratio == 0 {
+                        assert_eq!(share.value, 0);
+                    }
+                }
+            }
+        }
+
+        let shares = Amount::new(100, Currency::NOK)
+            .allocate(&[1, 1, 1])
+            .unwrap();
+        let values: Vec<u64> = shares.iter().map(|share| share.value).collect();
+        assert_eq!(values, vec![34, 33, 33]);
+    }
+
+    #[test]
+    fn allocate_rejects_zero_ratios() {
+        let amount = Amount::new(100, Currency::NOK);
+        assert!(amount.allocate(&[]).is_err());
+        assert!(amount.allocate(&[0, 0]).is_err());
+        assert!(amount.split(0).is_err());
+    }
+
+    #[test]
+    fn split_adds_up() {
+        for value in [0, 1, 7, 100, 1_000_003, u64::MAX] {
+            for parts in 1..=7 {
+                let shares = Amount::new(value, Currency::NOK).split(parts).unwrap();
+                let values: Vec<u64> = shares.iter().map(|share| share.value).collect();
+                assert_eq!(values.len(), parts);
+                assert_eq!(
+                    values.iter().map(|value| u128::from(*value)).sum::<u128>(),
+                    u128::from(value)
+                );
+                let (min, max) = (values.iter().min().unwrap(), values.iter().max().unwrap());
+                assert!(max - min <= 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! A synchronous `Gateway` for code that does not run inside an async runtime. Each call blocks
+//! the current thread until the async `Gateway` has completed the request, so it must not be
+//! used from within an async runtime.
+
+use crate::{
+    error::Error, payment, Amount, BrowserInfo, CardVerification, Currency, Environment,
+    Installments, LineItem, MerchantAccount, MerchantReference, MpiData, NetworkTxReference,
+    PaymentDetails, PaymentMethods, PspReference, RecurringProcessingModel, Secret, Session,
+    SessionResult, ShopperDetails, ShopperReference, StorePaymentMethodMode, StoredPaymentMethod,
+    StoredPaymentMethodId, ThreeDSOptions,
+};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+pub struct Gateway {
+    inner: crate::Gateway,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Gateway {
+    pub fn new(environment: Environment, timeout: Option<Duration>) -> Result<Gateway, Error> {
+        Gateway::from_async(crate::Gateway::new(environment, timeout)?)
+    }
+
+    /// Wraps an async `Gateway`, e.g. one made with `GatewayBuilder`.
+    pub fn from_async(inner: crate::Gateway) -> Result<Gateway, Error> {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(Error::Unspecified(format!(
+                    "could not create runtime ({})",
+                    err
+                )))
+            }
+        };
+
+        Ok(Gateway { inner, runtime })
+    }
+
+    pub fn rotate_api_key(&self, api_key: impl Into<Secret>) -> Result<(), Error> {
+        self.inner.rotate_api_key(api_key)
+    }
+
+    #[cfg(feature = "apple-pay")]
+    pub fn pay_with_apple_pay<'a>(
+        &self,
+        amount: &'a Amount,
+        apple_pay_token: &'a str,
+        reference: &'a MerchantReference,
+        return_url: &'a str,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_apple_pay(
+            amount,
+            apple_pay_token,
+            reference,
+            return_url,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_with_card_on_file<'a>(
+        &self,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        stored_payment_method_id: &'a StoredPaymentMethodId,
+        recurring_processing_model: RecurringProcessingModel,
+        network_tx_reference: &'a Option<&'a NetworkTxReference>,
+        installments: &'a Option<&'a Installments>,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_card_on_file(
+            amount,
+            currency,
+            reference,
+            shopper_reference,
+            stored_payment_method_id,
+            recurring_processing_model,
+            network_tx_reference,
+            installments,
+            return_url,
+            channel,
+            browser_info,
+            shopper_email,
+            shopper_i_p,
+            origin,
+            three_d_s,
+            mpi_data,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[cfg(feature = "google-pay")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_with_google_pay<'a>(
+        &self,
+        amount: &'a Amount,
+        google_pay_token: &'a str,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_google_pay(
+            amount,
+            google_pay_token,
+            reference,
+            shopper_reference,
+            return_url,
+            channel,
+            browser_info,
+            shopper_email,
+            shopper_i_p,
+            origin,
+            three_d_s,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_with_new_card_on_file<'a>(
+        &self,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        recurring_processing_model: RecurringProcessingModel,
+        installments: &'a Option<&'a Installments>,
+        encrypted_card_number: &'a str,
+        encrypted_expiry_month: &'a str,
+        encrypted_expiry_year: &'a str,
+        encrypted_security_code: &'a str,
+        holder_name: &'a Option<&'a str>,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_new_card_on_file(
+            amount,
+            currency,
+            reference,
+            shopper_reference,
+            recurring_processing_model,
+            installments,
+            encrypted_card_number,
+            encrypted_expiry_month,
+            encrypted_expiry_year,
+            encrypted_security_code,
+            holder_name,
+            return_url,
+            channel,
+            browser_info,
+            shopper_email,
+            shopper_i_p,
+            origin,
+            three_d_s,
+            mpi_data,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[cfg(feature = "swish")]
+    pub fn pay_with_swish<'a>(
+        &self,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        return_url: &'a str,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_swish(
+            amount,
+            currency,
+            reference,
+            return_url,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[cfg(feature = "vipps")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_with_vipps<'a>(
+        &self,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        return_url: &'a str,
+        channel: &'a str,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.pay_with_vipps(
+            amount,
+            currency,
+            reference,
+            return_url,
+            channel,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_new_card_on_file<'a>(
+        &self,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        recurring_processing_model: RecurringProcessingModel,
+        encrypted_card_number: &'a str,
+        encrypted_expiry_month: &'a str,
+        encrypted_expiry_year: &'a str,
+        encrypted_security_code: &'a str,
+        holder_name: &'a Option<&'a str>,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<CardVerification, Error> {
+        self.runtime.block_on(self.inner.store_new_card_on_file(
+            currency,
+            reference,
+            shopper_reference,
+            recurring_processing_model,
+            encrypted_card_number,
+            encrypted_expiry_month,
+            encrypted_expiry_year,
+            encrypted_security_code,
+            holder_name,
+            return_url,
+            channel,
+            browser_info,
+            shopper_email,
+            shopper_i_p,
+            origin,
+            three_d_s,
+            mpi_data,
+            shopper_details,
+            merchant_account,
+        ))
+    }
+
+    pub fn payment_details<'a>(
+        &self,
+        details: &'a PaymentDetails,
+        payment_data: &'a Option<&'a str>,
+        three_d_s_authentication_only: bool,
+    ) -> Result<payment::Response, Error> {
+        self.runtime.block_on(self.inner.payment_details(
+            details,
+            payment_data,
+            three_d_s_authentication_only,
+        ))
+    }
+
+    pub fn set_payment_details(&self, three_d_s_result: &str) -> Result<payment::Response, Error> {
+        self.runtime
+            .block_on(self.inner.set_payment_details(three_d_s_result))
+    }
+
+    pub fn set_redirect_result(&self, redirect_result: &str) -> Result<payment::Response, Error> {
+        self.runtime
+            .block_on(self.inner.set_redirect_result(redirect_result))
+    }
+
+    pub fn refund<'a>(
+        &self,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        psp_reference: &'a PspReference,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<(), Error> {
+        self.runtime.block_on(self.inner.refund(
+            amount,
+            currency,
+            reference,
+            psp_reference,
+            merchant_account,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn payment_methods<'a>(
+        &self,
+        country_code: &'a Option<&'a str>,
+        shopper_locale: &'a Option<&'a str>,
+        amount: &'a Option<&'a Amount>,
+        channel: &'a Option<&'a str>,
+        shopper_reference: &'a Option<&'a ShopperReference>,
+        allowed_payment_methods: &'a [&'a str],
+        blocked_payment_methods: &'a [&'a str],
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<PaymentMethods, Error> {
+        self.runtime.block_on(self.inner.payment_methods(
+            country_code,
+            shopper_locale,
+            amount,
+            channel,
+            shopper_reference,
+            allowed_payment_methods,
+            blocked_payment_methods,
+            merchant_account,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_session<'a>(
+        &self,
+        amount: &'a Amount,
+        reference: &'a MerchantReference,
+        return_url: &'a str,
+        expires_at: &'a Option<DateTime<Utc>>,
+        country_code: &'a Option<&'a str>,
+        shopper_locale: &'a Option<&'a str>,
+        shopper_reference: &'a Option<&'a ShopperReference>,
+        shopper_email: &'a Option<&'a str>,
+        channel: &'a Option<&'a str>,
+        allowed_payment_methods: &'a [&'a str],
+        store_payment_method_mode: StorePaymentMethodMode,
+        line_items: &'a [LineItem],
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<Session, Error> {
+        self.runtime.block_on(self.inner.create_session(
+            amount,
+            reference,
+            return_url,
+            expires_at,
+            country_code,
+            shopper_locale,
+            shopper_reference,
+            shopper_email,
+            channel,
+            allowed_payment_methods,
+            store_payment_method_mode,
+            line_items,
+            merchant_account,
+        ))
+    }
+
+    pub fn session_result(
+        &self,
+        session_id: &str,
+        session_result: &str,
+    ) -> Result<SessionResult, Error> {
+        self.runtime
+            .block_on(self.inner.session_result(session_id, session_result))
+    }
+
+    pub fn stored_payment_methods(
+        &self,
+        shopper_reference: &ShopperReference,
+        merchant_account: &Option<&MerchantAccount>,
+    ) -> Result<Vec<StoredPaymentMethod>, Error> {
+        self.runtime.block_on(
+            self.inner
+                .stored_payment_methods(shopper_reference, merchant_account),
+        )
+    }
+
+    pub fn delete_stored_payment_method(
+        &self,
+        stored_payment_method_id: &StoredPaymentMethodId,
+        shopper_reference: &ShopperReference,
+        merchant_account: &Option<&MerchantAccount>,
+    ) -> Result<(), Error> {
+        self.runtime
+            .block_on(self.inner.delete_stored_payment_method(
+                stored_payment_method_id,
+                shopper_reference,
+                merchant_account,
+            ))
+    }
+
+    #[cfg(feature = "apple-pay")]
+    pub fn make_apple_pay_session<'a>(
+        &self,
+        country_code: &'a str,
+        amount: &'a Amount,
+        channel: &'a str,
+        display_name: &'a str,
+        domain_name: &'a str,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<String, Error> {
+        self.runtime.block_on(self.inner.make_apple_pay_session(
+            country_code,
+            amount,
+            channel,
+            display_name,
+            domain_name,
+            merchant_account,
+        ))
+    }
+}
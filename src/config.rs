@@ -0,0 +1,415 @@
+use crate::{error::Error, MerchantAccount, Secret};
+#[cfg(feature = "client")]
+use crate::{Environment, Gateway, GatewayBuilder, RetryPolicy};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::path::Path;
+#[cfg(feature = "client")]
+use std::time::Duration;
+
+/// Which Adyen environment a `GatewayConfig` connects to.
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvironmentKind {
+    Test,
+    Live,
+}
+
+/// The settings of a `Gateway`, loadable from JSON, TOML (with the `toml` feature) or
+/// environment variables. Each field is read from the environment variable of the same name,
+/// upper-cased and prefixed with `ADYEN_`:
+///
+/// | Field                  | Variable                     | Example                |
+/// |------------------------|------------------------------|------------------------|
+/// | `environment`          | `ADYEN_ENVIRONMENT`          | `test` or `live`       |
+/// | `live_url_prefix`      | `ADYEN_LIVE_URL_PREFIX`      | `1797a841fbb37ca7-Co`  |
+/// | `api_key`              | `ADYEN_API_KEY`              |                        |
+/// | `merchant_account`     | `ADYEN_MERCHANT_ACCOUNT`     | `ShopNO`               |
+/// | `hmac_keys`            | `ADYEN_HMAC_KEYS`            | comma-separated hex    |
+/// | `timeout_secs`         | `ADYEN_TIMEOUT_SECS`         | `60`                   |
+/// | `connect_timeout_secs` | `ADYEN_CONNECT_TIMEOUT_SECS` | `10`                   |
+/// | `max_retries`          | `ADYEN_MAX_RETRIES`          | `2`                    |
+/// | `retry_backoff_ms`     | `ADYEN_RETRY_BACKOFF_MS`     | `500`                  |
+///
+/// It is also available without the `client` feature, e.g. to read `hmac_keys` for webhooks.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GatewayConfig {
+    pub environment: EnvironmentKind,
+
+    /// Required for live, e.g. "1797a841fbb37ca7-AdyenDemo".
+    /// https://docs.adyen.com/development-resources/live-endpoints/
+    #[serde(default)]
+    pub live_url_prefix: Option<String>,
+
+    pub api_key: Secret,
+
+    #[serde(default)]
+    pub merchant_account: Option<MerchantAccount>,
+
+    /// The HMAC keys webhooks are signed with, for `Webhook::from_signed_json`. More than one
+    /// can be configured while a key is being rotated.
+    #[serde(default)]
+    pub hmac_keys: Vec<Secret>,
+
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+
+    #[serde(default)]
+    pub retry_backoff_ms: Option<u64>,
+}
+
+const ENV_PREFIX: &str = "ADYEN_";
+
+enum Kind {
+    String,
+    Number,
+    List,
+}
+
+const ENV_FIELDS: &[(&str, Kind)] = &[
+    ("environment", Kind::String),
+    ("live_url_prefix", Kind::String),
+    ("api_key", Kind::String),
+    ("merchant_account", Kind::String),
+    ("hmac_keys", Kind::List),
+    ("timeout_secs", Kind::Number),
+    ("connect_timeout_secs", Kind::Number),
+    ("max_retries", Kind::Number),
+    ("retry_backoff_ms", Kind::Number),
+];
+
+impl GatewayConfig {
+    /// Reads the `ADYEN_*` environment variables.
+    pub fn from_env() -> Result<GatewayConfig, Error> {
+        let mut fields = Map::new();
+
+        for (field, kind) in ENV_FIELDS {
+            let name = format!("{}{}", ENV_PREFIX, field.to_uppercase());
+            let value = match std::env::var(&name) {
+                Ok(value) => value,
+                Err(std::env::VarError::NotPresent) => continue,
+                Err(err) => {
+                    return Err(Error::ConfigurationError(format!("{} ({})", name, err)));
+                }
+            };
+
+            let value = match kind {
+                Kind::String => Value::String(value),
+                Kind::Number => match value.trim().parse::<u64>() {
+                    Ok(number) => Value::from(number),
+                    Err(_) => {
+                        return Err(Error::ConfigurationError(format!(
+                            "{} must be a whole number",
+                            name
+                        )))
+                    }
+                },
+                Kind::List => Value::Array(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(|item| Value::String(item.to_string()))
+                        .collect(),
+                ),
+            };
+            fields.insert(field.to_string(), value);
+        }
+
+        GatewayConfig::parsed(serde_json::from_value(Value::Object(fields)))
+    }
+
+    pub fn from_json_str(g: &str) -> Result<GatewayConfig, Error> {
+        GatewayConfig::parsed(serde_json::from_str(g))
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(g: &str) -> Result<GatewayConfig, Error> {
+        GatewayConfig::parsed(toml::from_str(g))
+    }
+
+    /// Reads a `.json` file, or a `.toml` file with the `toml` feature.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<GatewayConfig, Error> {
+        let path = path.as_ref();
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                return Err(Error::ConfigurationError(format!(
+                    "could not read {} ({})",
+                    path.display(),
+                    err
+                )))
+            }
+        };
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => GatewayConfig::from_json_str(&text),
+            #[cfg(feature = "toml")]
+            Some("toml") => GatewayConfig::from_toml_str(&text),
+            _ => Err(Error::ConfigurationError(format!(
+                "unsupported configuration file {}",
+                path.display()
+            ))),
+        }
+    }
+
+    fn parsed<E: std::fmt::Display>(
+        config: Result<GatewayConfig, E>,
+    ) -> Result<GatewayConfig, Error> {
+        let config = match config {
+            Ok(config) => config,
+            Err(err) => return Err(Error::ConfigurationError(err.to_string())),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut errors = Vec::new();
+
+        match (self.environment, &self.live_url_prefix) {
+            (EnvironmentKind::Live, None) => {
+                errors.push(String::from("live_url_prefix is required for live"))
+            }
+            (EnvironmentKind::Live, Some(prefix)) if prefix.trim().is_empty() => {
+                errors.push(String::from("live_url_prefix must not be empty"))
+            }
+            (EnvironmentKind::Test, Some(_)) => {
+                errors.push(String::from("live_url_prefix is only used for live"))
+            }
+            _ => {}
+        }
+
+        if self.api_key.is_empty() {
+            errors.push(String::from("api_key must not be empty"));
+        }
+
+        if let Some(merchant_account) = &self.merchant_account {
+            if merchant_account.as_str().trim().is_empty() {
+                errors.push(String::from("merchant_account must not be empty"));
+            }
+        }
+
+        // Adyen shows HMAC keys as hex in the Customer Area.
+        for hmac_key in &self.hmac_keys {
+            if hex::decode(hmac_key.expose()).is_err() {
+                errors.push(String::from("hmac_keys must be hexadecimal"));
+                break;
+            }
+        }
+
+        if self.timeout_secs == Some(0) {
+            errors.push(String::from("timeout_secs must be greater than 0"));
+        }
+        if self.connect_timeout_secs == Some(0) {
+            errors.push(String::from("connect_timeout_secs must be greater than 0"));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ConfigurationError(errors.join(", ")))
+        }
+    }
+
+    #[cfg(feature = "client")]
+    pub fn environment(&self) -> Environment {
+        match (self.environment, &self.live_url_prefix) {
+            (EnvironmentKind::Live, Some(url_prefix)) => Environment::Live {
+                api_key: self.api_key.clone(),
+                url_prefix: url_prefix.clone(),
+            },
+            _ => Environment::Test {
+                api_key: self.api_key.clone(),
+            },
+        }
+    }
+
+    #[cfg(feature = "client")]
+    pub fn builder(&self) -> Result<GatewayBuilder, Error> {
+        self.validate()?;
+
+        let mut builder = GatewayBuilder::new(self.environment());
+
+        if let Some(timeout_secs) = self.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(timeout_secs));
+        }
+        if let Some(connect_timeout_secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(connect_timeout_secs));
+        }
+
+        let mut retry_policy = RetryPolicy::default();
+        if let Some(max_retries) = self.max_retries {
+            retry_policy.max_retries = max_retries;
+        }
+        if let Some(retry_backoff_ms) = self.retry_backoff_ms {
+            retry_policy.initial_backoff = Duration::from_millis(retry_backoff_ms);
+        }
+        builder = builder.retry_policy(retry_policy);
+
+        if let Some(merchant_account) = &self.merchant_account {
+            builder = builder.merchant_account(merchant_account.clone());
+        }
+
+        Ok(builder)
+    }
+
+    #[cfg(feature = "client")]
+    pub fn build(&self) -> Result<Gateway, Error> {
+        self.builder()?.build()
+    }
+}
+
+#[cfg(feature = "client")]
+impl Gateway {
+    /// Builds a `Gateway` from the `ADYEN_*` environment variables described on `GatewayConfig`.
+    /// Use `GatewayConfig::from_env` to also read the webhook HMAC keys.
+    pub fn from_env() -> Result<Gateway, Error> {
+        GatewayConfig::from_env()?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "environment": "live",
+        "live_url_prefix": "1797a841fbb37ca7-AdyenDemo",
+        "api_key": "api-key",
+        "merchant_account": "ShopNO",
+        "hmac_keys": ["44782DEF", "0A1B"],
+        "timeout_secs": 60,
+        "max_retries": 2
+    }"#;
+
+    fn assert_parsed(config: &GatewayConfig) {
+        assert_eq!(config.environment, EnvironmentKind::Live);
+        assert_eq!(
+            config.live_url_prefix.as_deref(),
+            Some("1797a841fbb37ca7-AdyenDemo")
+        );
+        assert_eq!(config.api_key.expose(), "api-key");
+        assert_eq!(
+            config
+                .merchant_account
+                .as_ref()
+                .map(MerchantAccount::as_str),
+            Some("ShopNO")
+        );
+        let hmac_keys: Vec<&str> = config.hmac_keys.iter().map(Secret::expose).collect();
+        assert_eq!(hmac_keys, ["44782DEF", "0A1B"]);
+        assert_eq!(config.timeout_secs, Some(60));
+        assert_eq!(config.connect_timeout_secs, None);
+        assert_eq!(config.max_retries, Some(2));
+        assert_eq!(config.retry_backoff_ms, None);
+    }
+
+    #[test]
+    fn parses_json() {
+        assert_parsed(&GatewayConfig::from_json_str(JSON).unwrap());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn parses_toml() {
+        let config = GatewayConfig::from_toml_str(
+            r#"
+            environment = "live"
+            live_url_prefix = "1797a841fbb37ca7-AdyenDemo"
+            api_key = "api-key"
+            merchant_account = "ShopNO"
+            hmac_keys = ["44782DEF", "0A1B"]
+            timeout_secs = 60
+            max_retries = 2
+            "#,
+        )
+        .unwrap();
+        assert_parsed(&config);
+    }
+
+    #[test]
+    fn parses_files() {
+        let path = std::env::temp_dir().join(format!("adyen-config-{}.json", std::process::id()));
+        std::fs::write(&path, JSON).unwrap();
+        let config = GatewayConfig::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_parsed(&config.unwrap());
+
+        assert!(matches!(
+            GatewayConfig::from_file(path.with_extension("yaml")),
+            Err(Error::ConfigurationError(_))
+        ));
+        assert!(matches!(
+            GatewayConfig::from_file(&path),
+            Err(Error::ConfigurationError(_))
+        ));
+    }
+
+    // The only test that sets `ADYEN_*` variables, as they are shared by all tests.
+    #[test]
+    fn parses_env() {
+        let variables = [
+            ("ADYEN_ENVIRONMENT", "live"),
+            ("ADYEN_LIVE_URL_PREFIX", "1797a841fbb37ca7-AdyenDemo"),
+            ("ADYEN_API_KEY", "api-key"),
+            ("ADYEN_MERCHANT_ACCOUNT", "ShopNO"),
+            ("ADYEN_HMAC_KEYS", " 44782DEF, 0A1B ,"),
+            ("ADYEN_TIMEOUT_SECS", "60"),
+            ("ADYEN_MAX_RETRIES", " 2 "),
+        ];
+        for (name, value) in variables {
+            std::env::set_var(name, value);
+        }
+        let config = GatewayConfig::from_env();
+
+        std::env::set_var("ADYEN_TIMEOUT_SECS", "1m");
+        let invalid = GatewayConfig::from_env();
+
+        for (name, _) in variables {
+            std::env::remove_var(name);
+        }
+
+        assert_parsed(&config.unwrap());
+        assert!(
+            matches!(invalid, Err(Error::ConfigurationError(g)) if g.contains("ADYEN_TIMEOUT_SECS"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        for json in [
+            r#"{"environment": "live", "api_key": "api-key"}"#,
+            r#"{"environment": "live", "live_url_prefix": " ", "api_key": "api-key"}"#,
+            r#"{"environment": "test", "live_url_prefix": "prefix", "api_key": "api-key"}"#,
+            r#"{"environment": "test", "api_key": ""}"#,
+            r#"{"environment": "test", "api_key": "api-key", "merchant_account": ""}"#,
+            r#"{"environment": "test", "api_key": "api-key", "hmac_keys": ["not hex"]}"#,
+            r#"{"environment": "test", "api_key": "api-key", "timeout_secs": 0}"#,
+            r#"{"environment": "test", "api_key": "api-key", "connect_timeout_secs": 0}"#,
+            r#"{"environment": "test", "api_key": "api-key", "unknown": 1}"#,
+            r#"{"environment": "staging", "api_key": "api-key"}"#,
+        ] {
+            assert!(
+                matches!(
+                    GatewayConfig::from_json_str(json),
+                    Err(Error::ConfigurationError(_))
+                ),
+                "{}",
+                json
+            );
+        }
+
+        assert!(
+            GatewayConfig::from_json_str(r#"{"environment": "test", "api_key": "api-key"}"#)
+                .is_ok()
+        );
+    }
+}
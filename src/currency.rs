@@ -1,42 +1,264 @@
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt};
-
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Currency {
-    NOK,
-    SEK,
-    DKK,
-    ISK,
-    GBP,
-    EUR,
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+/// The error returned when a string is not a currency code supported by Adyen.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseCurrencyError(pub String);
+
+impl std::error::Error for ParseCurrencyError {}
+
+impl fmt::Display for ParseCurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsupported currency code \"{}\"", self.0)
+    }
+}
+
+macro_rules! currencies {
+    ($($code:ident => $numeric:expr, $exponent:expr, $symbol:expr, $name:expr;)*) => {
+        /// The ISO 4217 currencies supported by Adyen.
+        /// https://docs.adyen.com/development-resources/currency-codes/
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+        pub enum Currency {
+            $(
+                #[doc = $name]
+                $code,
+            )*
+        }
+
+        impl Currency {
+            /// All supported currencies. A function rather than a constant, since a constant named
+            /// `ALL` would be shadowed by the Albanian lek.
+            pub fn all() -> &'static [Currency] {
+                &[$(Currency::$code),*]
+            }
+
+            /// The ISO 4217 alphabetic code, e.g. "NOK".
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Currency::$code => stringify!($code),)*
+                }
+            }
+
+            /// The ISO 4217 numeric code, e.g. 578 for NOK.
+            pub fn numeric_code(&self) -> u16 {
+                match self {
+                    $(Currency::$code => $numeric,)*
+                }
+            }
+
+            /// The number of decimals Adyen uses for minor units, e.g. 2 for NOK (øre) and 0 for
+            /// JPY. This can differ from ISO 4217.
+            pub fn exponent(&self) -> u32 {
+                match self {
+                    $(Currency::$code => $exponent,)*
+                }
+            }
+
+            /// The symbol used when displaying amounts, e.g. "kr" or "€". Falls back to the
+            /// alphabetic code for currencies without a widely used symbol.
+            pub fn symbol(&self) -> &'static str {
+                match self {
+                    $(Currency::$code => $symbol,)*
+                }
+            }
+
+            /// The English name of the currency.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Currency::$code => $name,)*
+                }
+            }
+        }
+
+        impl FromStr for Currency {
+            type Err = ParseCurrencyError;
+
+            fn from_str(g: &str) -> Result<Self, Self::Err> {
+                match g {
+                    $(stringify!($code) => Ok(Currency::$code),)*
+                    _ => Err(ParseCurrencyError(g.to_string())),
+                }
+            }
+        }
+    };
+}
+
+currencies! {
+    AED => 784, 2, "AED", "UAE Dirham";
+    ALL => 8, 2, "L", "Albanian Lek";
+    AMD => 51, 2, "֏", "Armenian Dram";
+    ANG => 532, 2, "ƒ", "Antillian Guilder";
+    AOA => 973, 2, "Kz", "Angolan Kwanza";
+    ARS => 32, 2, "$", "Argentine Peso";
+    AUD => 36, 2, "$", "Australian Dollar";
+    AWG => 533, 2, "ƒ", "Aruban Guilder";
+    AZN => 944, 2, "₼", "Azerbaijani Manat";
+    BAM => 977, 2, "KM", "Bosnia and Herzegovina Convertible Marks";
+    BBD => 52, 2, "$", "Barbados Dollar";
+    BDT => 50, 2, "৳", "Bangladesh Taka";
+    BGN => 975, 2, "лв", "Bulgarian Lev";
+    BHD => 48, 3, "BHD", "Bahraini Dinar";
+    BMD => 60, 2, "$", "Bermudian Dollar";
+    BND => 96, 2, "$", "Brunei Dollar";
+    BOB => 68, 2, "Bs", "Bolivian Boliviano";
+    BRL => 986, 2, "R$", "Brazilian Real";
+    BSD => 44, 2, "$", "Bahamian Dollar";
+    BWP => 72, 2, "P", "Botswana Pula";
+    BYN => 933, 2, "Br", "New Belarusian Ruble";
+    BZD => 84, 2, "$", "Belize Dollar";
+    CAD => 124, 2, "$", "Canadian Dollar";
+    CHF => 756, 2, "CHF", "Swiss Franc";
+    CLP => 152, 2, "$", "Chilean Peso";
+    CNY => 156, 2, "¥", "Yuan Renminbi";
+    COP => 170, 2, "$", "Colombian Peso";
+    CRC => 188, 2, "₡", "Costa Rican Colon";
+    CUP => 192, 2, "$", "Cuban Peso";
+    CVE => 132, 0, "$", "Cape Verdi Escudo";
+    CZK => 203, 2, "Kč", "Czech Koruna";
+    DJF => 262, 0, "Fdj", "Djibouti Franc";
+    DKK => 208, 2, "kr", "Danish Krone";
+    DOP => 214, 2, "$", "Dominican Republic Peso";
+    DZD => 12, 2, "DZD", "Algerian Dinar";
+    EGP => 818, 2, "£", "Egyptian Pound";
+    ETB => 230, 2, "Br", "Ethiopian Birr";
+    EUR => 978, 2, "€", "Euro";
+    FJD => 242, 2, "$", "Fiji Dollar";
+    FKP => 238, 2, "£", "Falkland Islands Pound";
+    GBP => 826, 2, "£", "Pound Sterling";
+    GEL => 981, 2, "₾", "Georgian Lari";
+    GHS => 936, 2, "₵", "Ghanaian Cedi";
+    GIP => 292, 2, "£", "Gibraltar Pound";
+    GMD => 270, 2, "D", "Gambia Delasi";
+    GNF => 324, 0, "FG", "Guinea Franc";
+    GTQ => 320, 2, "Q", "Guatemala Quetzal";
+    GYD => 328, 2, "$", "Guyanese Dollar";
+    HKD => 344, 2, "$", "Hong Kong Dollar";
+    HNL => 340, 2, "L", "Honduras Lempira";
+    HTG => 332, 2, "G", "Haitian Gourde";
+    HUF => 348, 2, "Ft", "Hungarian Forint";
+    IDR => 360, 0, "Rp", "Indonesian Rupiah";
+    ILS => 376, 2, "₪", "New Israeli Sheqel";
+    INR => 356, 2, "₹", "Indian Rupee";
+    IQD => 368, 3, "IQD", "Iraqi Dinar";
+    ISK => 352, 0, "kr", "Iceland Krona";
+    JMD => 388, 2, "$", "Jamaican Dollar";
+    JOD => 400, 3, "JOD", "Jordanian Dinar";
+    JPY => 392, 0, "¥", "Japanese Yen";
+    KES => 404, 2, "KSh", "Kenyan Shilling";
+    KGS => 417, 2, "сом", "Kyrgyzstan Som";
+    KHR => 116, 2, "៛", "Riel";
+    KMF => 174, 0, "CF", "Comoro Franc";
+    KRW => 410, 0, "₩", "South-Korean Won";
+    KWD => 414, 3, "KWD", "Kuwaiti Dinar";
+    KYD => 136, 2, "$", "Cayman Islands Dollar";
+    KZT => 398, 2, "₸", "Kazakhstani Tenge";
+    LAK => 418, 2, "₭", "Laos Kip";
+    LBP => 422, 2, "LBP", "Lebanese Pound";
+    LKR => 144, 2, "Rs", "Sri Lanka Rupee";
+    LYD => 434, 3, "LYD", "Libyan Dinar";
+    MAD => 504, 2, "MAD", "Moroccan Dirham";
+    MDL => 498, 2, "L", "Moldovia Leu";
+    MKD => 807, 2, "ден", "Macedonian Denar";
+    MMK => 104, 2, "K", "Myanmar Kyat";
+    MNT => 496, 2, "₮", "Mongolia Tugrik";
+    MOP => 446, 2, "MOP$", "Macao Pataca";
+    MRU => 929, 2, "UM", "Mauritania Ouguiya";
+    MUR => 480, 2, "Rs", "Mauritius Rupee";
+    MVR => 462, 2, "Rf", "Maldives Rufiyaa";
+    MWK => 454, 2, "MK", "Malawi Kwacha";
+    MXN => 484, 2, "$", "Mexican Peso";
+    MYR => 458, 2, "RM", "Malaysian Ringgit";
+    MZN => 943, 2, "MT", "Mozambican Metical";
+    NAD => 516, 2, "$", "Namibian Dollar";
+    NGN => 566, 2, "₦", "Nigerian Naira";
+    NIO => 558, 2, "C$", "Nicaragua Cordoba Oro";
+    NOK => 578, 2, "kr", "Norwegian Krone";
+    NPR => 524, 2, "Rs", "Nepalese Rupee";
+    NZD => 554, 2, "$", "New Zealand Dollar";
+    OMR => 512, 3, "OMR", "Rial Omani";
+    PAB => 590, 2, "B/.", "Panamanian Balboa";
+    PEN => 604, 2, "S/", "Peruvian Nuevo Sol";
+    PGK => 598, 2, "K", "New Guinea Kina";
+    PHP => 608, 2, "₱", "Philippine Peso";
+    PKR => 586, 2, "Rs", "Pakistan Rupee";
+    PLN => 985, 2, "zł", "Polish Zloty";
+    PYG => 600, 0, "₲", "Paraguayan Guarani";
+    QAR => 634, 2, "QAR", "Qatari Rial";
+    RON => 946, 2, "lei", "New Romanian Lei";
+    RSD => 941, 2, "дин.", "Serbian Dinar";
+    RUB => 643, 2, "₽", "Russian Ruble";
+    RWF => 646, 0, "FRw", "Rwanda Franc";
+    SAR => 682, 2, "SAR", "Saudi Riyal";
+    SBD => 90, 2, "$", "Solomon Island Dollar";
+    SCR => 690, 2, "Rs", "Seychelles Rupee";
+    SEK => 752, 2, "kr", "Swedish Krona";
+    SGD => 702, 2, "$", "Singapore Dollar";
+    SHP => 654, 2, "£", "St Helena Pound";
+    SLE => 925, 2, "Le", "Sierra Leone Leone";
+    SOS => 706, 2, "Sh", "Somali Shilling";
+    STN => 930, 2, "Db", "Sao Tome and Principe Dobra";
+    SVC => 222, 2, "₡", "El Salvador Colón";
+    SZL => 748, 2, "E", "Swaziland Lilangeni";
+    THB => 764, 2, "฿", "Thai Baht";
+    TND => 788, 3, "TND", "Tunisian Dinar";
+    TOP => 776, 2, "T$", "Tonga Pa'anga";
+    TRY => 949, 2, "₺", "New Turkish Lira";
+    TTD => 780, 2, "$", "Trinidad and Tobago Dollar";
+    TWD => 901, 2, "NT$", "New Taiwan Dollar";
+    TZS => 834, 2, "TSh", "Tanzanian Shilling";
+    UAH => 980, 2, "₴", "Ukraine Hryvnia";
+    UGX => 800, 0, "USh", "Uganda Shilling";
+    USD => 840, 2, "$", "US Dollars";
+    UYU => 858, 2, "$", "Peso Uruguayo";
+    UZS => 860, 2, "UZS", "Uzbekistan Som";
+    VES => 928, 2, "Bs.", "Venezuelan Bolívar";
+    VND => 704, 0, "₫", "Vietnamese Dong";
+    VUV => 548, 0, "VT", "Vanuatu Vatu";
+    WST => 882, 2, "T", "Samoan Tala";
+    XAF => 950, 0, "FCFA", "CFA Franc BEAC";
+    XCD => 951, 2, "$", "East Caribbean Dollar";
+    XOF => 952, 0, "CFA", "CFA Franc BCEAO";
+    XPF => 953, 0, "₣", "CFP Franc";
+    YER => 886, 2, "YER", "Yemeni Rial";
+    ZAR => 710, 2, "R", "South African Rand";
+    ZMW => 967, 2, "ZK", "Zambian Kwacha";
 }
 
 impl TryFrom<&str> for Currency {
-    type Error = ();
+    type Error = ParseCurrencyError;
 
     fn try_from(g: &str) -> Result<Self, Self::Error> {
-        match g {
-            x if x == "NOK" => Ok(Currency::NOK),
-            x if x == "SEK" => Ok(Currency::SEK),
-            x if x == "DKK" => Ok(Currency::DKK),
-            x if x == "ISK" => Ok(Currency::ISK),
-            x if x == "GBP" => Ok(Currency::GBP),
-            x if x == "EUR" => Ok(Currency::EUR),
-            _ => Err(()),
-        }
+        g.parse()
     }
 }
 
 impl fmt::Display for Currency {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let text = match self {
-            Currency::NOK => "NOK",
-            Currency::SEK => "SEK",
-            Currency::DKK => "DKK",
-            Currency::ISK => "ISK",
-            Currency::GBP => "GBP",
-            Currency::EUR => "EUR",
-        };
-        write!(f, "{}", text)
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_lists_every_currency_once() {
+        let all = Currency::all();
+        assert!(all.contains(&Currency::ALL));
+        assert!(all.contains(&Currency::NOK));
+
+        let codes: HashSet<&str> = all.iter().map(Currency::code).collect();
+        assert_eq!(codes.len(), all.len());
+    }
+
+    #[test]
+    fn all_round_trip_through_their_codes() {
+        for currency in Currency::all() {
+            assert_eq!(&currency.code().parse::<Currency>().unwrap(), currency);
+            assert_eq!(currency.to_string(), currency.code());
+            assert!(currency.exponent() <= 3, "{}", currency);
+        }
     }
 }
@@ -1,3 +1,4 @@
+use crate::{payment::RefusalReason, Currency, PspReference, ValidationError};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -23,14 +24,12 @@ impl From<(u16, String, String, String, Option<String>)> for ApiError {
     #[track_caller]
     fn from(err: (u16, String, String, String, Option<String>)) -> Self {
         let (status, error_code, message, error_type, psp_reference) = err;
-        match error_code.as_str() {
-            _ => ApiError::Other {
-                status,
-                error_code,
-                message,
-                error_type,
-                psp_reference,
-            },
+        ApiError::Other {
+            status,
+            error_code,
+            message,
+            error_type,
+            psp_reference,
         }
     }
 }
@@ -46,6 +45,19 @@ pub enum Error {
     // ChecksumValidationError,
     ConversionError(String),
     UnsupportedPaymentMethod,
+    CardNotTokenised {
+        refusal_reason: Option<RefusalReason>,
+        psp_reference: Option<PspReference>,
+    },
+    CurrencyMismatch {
+        expected: Currency,
+        found: Currency,
+    },
+    ValidationError(ValidationError),
+    MissingMerchantAccount,
+    ConfigurationError(String),
+    EncryptionError(String),
+    InvalidSignature,
 }
 
 impl std::error::Error for Error {}
@@ -68,6 +80,31 @@ impl fmt::Display for Error {
                 g = String::from("unsupported payment method");
                 &g
             }
+            Error::CardNotTokenised { refusal_reason, .. } => {
+                g = match refusal_reason {
+                    Some(reason) => format!("card could not be tokenised ({:?})", reason),
+                    None => String::from("card could not be tokenised"),
+                };
+                &g
+            }
+            Error::CurrencyMismatch { expected, found } => {
+                g = format!("expected an amount in {}, got {}", expected, found);
+                &g
+            }
+            Error::ValidationError(err) => {
+                g = err.to_string();
+                &g
+            }
+            Error::ConfigurationError(g) => g,
+            Error::EncryptionError(g) => g,
+            Error::MissingMerchantAccount => {
+                g = String::from("no merchant account given, configured or routed");
+                &g
+            }
+            Error::InvalidSignature => {
+                g = String::from("webhook HMAC signature is invalid");
+                &g
+            }
         };
         write!(f, "{}", text)
     }
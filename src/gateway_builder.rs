@@ -0,0 +1,367 @@
+use crate::{
+    api_key_header, error::Error, Environment, Gateway, MerchantAccount, MerchantAccountRouting,
+};
+use std::{sync::RwLock, time::Duration};
+
+/// The Checkout API version used for each family of endpoints. Every family defaults to v71.
+/// https://docs.adyen.com/online-payments/upgrade-your-integration/
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ApiVersions {
+    /// `/payments` and `/payments/details`.
+    pub payments: u16,
+
+    /// `/paymentMethods`.
+    pub payment_methods: u16,
+
+    /// `/sessions`.
+    pub sessions: u16,
+
+    /// `/storedPaymentMethods`.
+    pub stored_payment_methods: u16,
+
+    /// `/payments/{paymentPspReference}/refunds` and other modifications.
+    pub modifications: u16,
+
+    /// `/applePay/sessions`.
+    pub utility: u16,
+}
+
+impl ApiVersions {
+    /// Uses `version` for every family of endpoints.
+    pub fn all(version: u16) -> ApiVersions {
+        ApiVersions {
+            payments: version,
+            payment_methods: version,
+            sessions: version,
+            stored_payment_methods: version,
+            modifications: version,
+            utility: version,
+        }
+    }
+}
+
+impl Default for ApiVersions {
+    fn default() -> ApiVersions {
+        ApiVersions::all(71)
+    }
+}
+
+/// How requests that Adyen did not process are retried: those that could not connect and those
+/// rejected with 429 Too Many Requests. The default is not to retry.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+
+    /// The wait before the first retry, doubled for each one after it.
+    pub initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub(crate) fn backoff(&self, retries: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retries))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// The TLS implementation used by the HTTP client the builder creates.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TlsBackend {
+    #[cfg(feature = "native-tls")]
+    NativeTls,
+
+    #[cfg(feature = "rustls-tls")]
+    Rustls,
+}
+
+pub struct GatewayBuilder {
+    environment: Environment,
+    api_versions: ApiVersions,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    user_agent: Option<String>,
+    tls_backend: Option<TlsBackend>,
+    client: Option<reqwest::Client>,
+    merchant_account: Option<MerchantAccount>,
+    merchant_account_routing: Option<MerchantAccountRouting>,
+    #[cfg(feature = "tracing")]
+    log_bodies: bool,
+}
+
+impl GatewayBuilder {
+    pub fn new(environment: Environment) -> GatewayBuilder {
+        GatewayBuilder {
+            environment,
+            api_versions: ApiVersions::default(),
+            timeout: None,
+            retry_policy: RetryPolicy::default(),
+            connect_timeout: None,
+            proxy: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            user_agent: None,
+            tls_backend: None,
+            client: None,
+            merchant_account: None,
+            merchant_account_routing: None,
+            #[cfg(feature = "tracing")]
+            log_bodies: false,
+        }
+    }
+
+    pub fn api_versions(mut self, api_versions: ApiVersions) -> GatewayBuilder {
+        self.api_versions = api_versions;
+        self
+    }
+
+    /// The timeout of each request, from sending it until the response body has been read.
+    /// Defaults to 60 seconds. Also applies when `client` is used.
+    pub fn timeout(mut self, timeout: Duration) -> GatewayBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> GatewayBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> GatewayBuilder {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> GatewayBuilder {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// How long an idle connection is kept in the pool.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> GatewayBuilder {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> GatewayBuilder {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> GatewayBuilder {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn tls_backend(mut self, tls_backend: TlsBackend) -> GatewayBuilder {
+        self.tls_backend = Some(tls_backend);
+        self
+    }
+
+    /// Sends requests with an existing client instead of creating one. The connection, proxy,
+    /// user agent and TLS options of the builder are then ignored.
+    pub fn client(mut self, client: reqwest::Client) -> GatewayBuilder {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the merchant account used when a request neither passes one nor is routed to another
+    /// one.
+    pub fn merchant_account(mut self, merchant_account: MerchantAccount) -> GatewayBuilder {
+        self.merchant_account = Some(merchant_account);
+        self
+    }
+
+    /// Sets the rule that picks the merchant account for requests that do not pass one.
+    pub fn merchant_account_routing(mut self, routing: MerchantAccountRouting) -> GatewayBuilder {
+        self.merchant_account_routing = Some(routing);
+        self
+    }
+
+    /// Logs request and response bodies as `debug` events in each request's span. Card data,
+    /// wallet tokens, emails, IP addresses and browser info are redacted.
+    #[cfg(feature = "tracing")]
+    pub fn log_bodies(mut self, log_bodies: bool) -> GatewayBuilder {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    pub fn build(self) -> Result<Gateway, Error> {
+        let base_api_url = match &self.environment {
+            Environment::Test { .. } => String::from("https://checkout-test.adyen.com"),
+            Environment::Live { url_prefix, .. } => {
+                format!(
+                    "https://{}-checkout-live.adyenpayments.com/checkout",
+                    url_prefix
+                )
+            }
+        };
+
+        let api_key = match self.environment {
+            Environment::Test { api_key } | Environment::Live { api_key, .. } => api_key,
+        };
+        api_key_header(&api_key)?;
+
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::ClientBuilder::new().https_only(true);
+
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(pool_idle_timeout);
+                }
+                if let Some(pool_max_idle_per_host) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder = match self.tls_backend {
+                    #[cfg(feature = "native-tls")]
+                    Some(TlsBackend::NativeTls) => builder.use_native_tls(),
+                    #[cfg(feature = "rustls-tls")]
+                    Some(TlsBackend::Rustls) => builder.use_rustls_tls(),
+                    None => builder,
+                };
+
+                match builder.build() {
+                    Ok(r) => r,
+                    Err(err) => {
+                        return Err(Error::Unspecified(format!(
+                            "could not create reqwest client ({})",
+                            err
+                        )))
+                    }
+                }
+            }
+        };
+
+        Ok(Gateway {
+            client,
+            base_api_url,
+            api_key: RwLock::new(api_key),
+            api_versions: self.api_versions,
+            timeout: self.timeout.unwrap_or(Duration::new(60, 0)),
+            retry_policy: self.retry_policy,
+            merchant_account: self.merchant_account,
+            merchant_account_routing: self.merchant_account_routing,
+            #[cfg(feature = "tracing")]
+            log_bodies: self.log_bodies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Secret;
+
+    fn test_environment() -> Environment {
+        Environment::Test {
+            api_key: Secret::from("api-key"),
+        }
+    }
+
+    #[test]
+    fn uses_defaults() {
+        let gateway = GatewayBuilder::new(test_environment()).build().unwrap();
+        assert_eq!(gateway.base_api_url, "https://checkout-test.adyen.com");
+        assert_eq!(gateway.api_versions, ApiVersions::all(71));
+        assert_eq!(gateway.timeout, Duration::from_secs(60));
+        assert_eq!(gateway.retry_policy.max_retries, 0);
+        assert_eq!(
+            gateway.retry_policy.initial_backoff,
+            Duration::from_millis(500)
+        );
+        assert!(gateway.merchant_account.is_none());
+        assert!(gateway.merchant_account_routing.is_none());
+        assert_eq!(
+            gateway.checkout_url(gateway.api_versions.payments, "/payments"),
+            "https://checkout-test.adyen.com/v71/payments"
+        );
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let api_versions = ApiVersions {
+            payments: 70,
+            ..ApiVersions::default()
+        };
+        let retry_policy = RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+        };
+        let gateway = GatewayBuilder::new(Environment::Live {
+            api_key: Secret::from("api-key"),
+            url_prefix: String::from("1797a841fbb37ca7-AdyenDemo"),
+        })
+        .api_versions(api_versions)
+        .timeout(Duration::from_secs(5))
+        .retry_policy(retry_policy)
+        .merchant_account(MerchantAccount::new("ShopNO").unwrap())
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            gateway.base_api_url,
+            "https://1797a841fbb37ca7-AdyenDemo-checkout-live.adyenpayments.com/checkout"
+        );
+        assert_eq!(gateway.api_versions, api_versions);
+        assert_eq!(gateway.api_versions.sessions, 71);
+        assert_eq!(gateway.timeout, Duration::from_secs(5));
+        assert_eq!(gateway.retry_policy, retry_policy);
+        assert_eq!(
+            gateway
+                .merchant_account
+                .as_ref()
+                .map(MerchantAccount::as_str),
+            Some("ShopNO")
+        );
+        assert_eq!(
+            gateway.checkout_url(gateway.api_versions.payments, "/payments"),
+            "https://1797a841fbb37ca7-AdyenDemo-checkout-live.adyenpayments.com/checkout/v70/payments"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_api_keys() {
+        let result = GatewayBuilder::new(Environment::Test {
+            api_key: Secret::from("api\nkey"),
+        })
+        .build();
+        assert!(matches!(result, Err(Error::Unspecified(_))));
+    }
+
+    #[test]
+    fn doubles_the_backoff() {
+        let retry_policy = RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+        };
+        assert_eq!(retry_policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(retry_policy.backoff(2), Duration::from_millis(400));
+        // The multiplier saturates instead of overflowing.
+        assert_eq!(
+            retry_policy.backoff(u32::MAX),
+            Duration::from_millis(100) * u32::MAX
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{error::Error, validation::Validator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+macro_rules! identifier {
+    ($(#[$doc:meta])* $name:ident, $validate:expr) => {
+        $(#[$doc])*
+        ///
+        /// Values built with `new` are checked against Adyen's constraints; values deserialized
+        /// from Adyen's responses and webhooks are accepted as-is.
+        #[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Returns an error if the value does not meet Adyen's constraints for the field.
+            pub fn new(value: impl Into<String>) -> Result<$name, Error> {
+                let value = value.into();
+                let validate: fn(Validator, &str) -> Validator = $validate;
+                validate(Validator::new(), &value).finish()?;
+                Ok($name(value))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                String::deserialize(deserializer).map($name)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(g: &str) -> Result<Self, Self::Err> {
+                $name::new(g)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(g: String) -> Result<Self, Self::Error> {
+                $name::new(g)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+
+            fn try_from(g: &str) -> Result<Self, Self::Error> {
+                $name::new(g)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(g: $name) -> Self {
+                g.0
+            }
+        }
+    };
+}
+
+identifier!(
+    /// Adyen's unique reference for a payment or modification, e.g. "NC6HT9CRT65ZGN82".
+    PspReference,
+    |validator, value| validator.psp_reference(value)
+);
+
+identifier!(
+    /// The merchant's reference for a payment, e.g. an order number. At most 80 characters.
+    MerchantReference,
+    |validator, value| validator.reference(value)
+);
+
+identifier!(
+    /// The merchant's unique reference for a shopper. Between 3 and 256 characters.
+    ShopperReference,
+    |validator, value| validator.shopper_reference(value)
+);
+
+identifier!(
+    /// The name of the merchant account a request is processed with.
+    MerchantAccount,
+    |validator, value| validator.merchant_account(value)
+);
+
+identifier!(
+    /// The token of a stored payment method (also known as the recurring detail reference).
+    StoredPaymentMethodId,
+    |validator, value| validator.not_empty("storedPaymentMethodId", value)
+);
+
+identifier!(
+    /// The card scheme's reference for a payment, returned for the initial customer-initiated
+    /// payment and sent with the merchant-initiated payments that follow.
+    NetworkTxReference,
+    |validator, value| validator.not_empty("networkTxReference", value)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_validates() {
+        assert!(MerchantReference::new("r".repeat(81)).is_err());
+        assert!(NetworkTxReference::new("").is_err());
+        assert!(MerchantReference::new("order-1").is_ok());
+    }
+
+    #[test]
+    fn deserialize_accepts_any_string() {
+        let merchant_reference: MerchantReference =
+            serde_json::from_value(serde_json::json!("r".repeat(81))).unwrap();
+        assert_eq!(merchant_reference.as_str().len(), 81);
+
+        let network_tx_reference: NetworkTxReference =
+            serde_json::from_value(serde_json::json!("")).unwrap();
+        assert_eq!(network_tx_reference.as_str(), "");
+    }
+}
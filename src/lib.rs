@@ -1,157 +1,329 @@
+// reqwest compiles without a TLS backend, but then every request to Adyen fails at runtime.
+#[cfg(all(
+    feature = "client",
+    not(any(feature = "native-tls", feature = "rustls-tls"))
+))]
+compile_error!("the client features need a TLS backend: enable \"native-tls\" or \"rustls-tls\"");
+
 use rust_decimal::prelude::*;
+#[cfg(feature = "client")]
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::time::Duration;
+#[cfg(feature = "client")]
+use std::{sync::RwLock, time::Duration};
 mod error;
 pub use error::Error;
+mod config;
+pub use config::{EnvironmentKind, GatewayConfig};
 mod currency;
-pub use currency::Currency;
+pub use currency::{Currency, ParseCurrencyError};
 mod action;
-pub use action::Action;
+pub use action::{
+    Action, AwaitAction, NativeRedirectAction, QrCodeAction, RedirectAction, SdkAction,
+    ThreeDS2Action, ThreeDS2Subtype, VoucherAction,
+};
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod browser_info;
 pub use browser_info::BrowserInfo;
+#[cfg(feature = "cse")]
+mod encryption;
+#[cfg(feature = "cse")]
+pub use encryption::{CardDetails, EncryptedCard, EncryptionFormat, EncryptionKey};
+#[cfg(feature = "client")]
+mod gateway_builder;
+#[cfg(feature = "client")]
+pub use gateway_builder::{ApiVersions, GatewayBuilder, RetryPolicy, TlsBackend};
+mod identifiers;
+pub use identifiers::{
+    MerchantAccount, MerchantReference, NetworkTxReference, PspReference, ShopperReference,
+    StoredPaymentMethodId,
+};
+mod line_item;
+pub use line_item::LineItem;
+mod locale;
+pub use locale::{Locale, ParseLocaleError};
+#[cfg(feature = "apple-pay")]
 mod make_apple_pay_session;
+#[cfg(feature = "apple-pay")]
 mod pay_with_apple_pay;
+#[cfg(feature = "client")]
 mod pay_with_card_on_file;
+#[cfg(feature = "google-pay")]
 mod pay_with_google_pay;
+#[cfg(feature = "client")]
 mod pay_with_new_card_on_file;
+#[cfg(feature = "swish")]
 mod pay_with_swish;
+#[cfg(feature = "vipps")]
 mod pay_with_vipps;
-mod payment;
+pub mod payment;
+#[cfg(feature = "client")]
+mod payment_details;
+#[cfg(feature = "client")]
+pub use payment_details::PaymentDetails;
+#[cfg(feature = "client")]
+mod payment_methods;
+#[cfg(feature = "client")]
+pub use payment_methods::{Configuration, Issuer, PaymentMethod, PaymentMethods};
+mod recurring;
+pub use recurring::{Installments, RecurringProcessingModel};
+#[cfg(feature = "client")]
 mod refund;
+#[cfg(feature = "client")]
+mod routing;
+#[cfg(feature = "client")]
+pub use routing::{MerchantAccountRouting, RoutingContext};
+mod secret;
+pub use secret::Secret;
+#[cfg(feature = "client")]
+mod sessions;
+#[cfg(feature = "client")]
+pub use sessions::{Session, SessionResult, SessionStatus, StorePaymentMethodMode};
+#[cfg(feature = "client")]
 mod set_payment_details;
+#[cfg(feature = "client")]
 mod set_redirect_result;
+mod shopper;
+pub use shopper::{Address, Name, RiskData, ShopperDetails};
+#[cfg(feature = "client")]
+mod store_new_card_on_file;
+#[cfg(feature = "client")]
+pub use store_new_card_on_file::CardVerification;
+#[cfg(feature = "client")]
+mod stored_payment_methods;
+#[cfg(feature = "client")]
+pub use stored_payment_methods::StoredPaymentMethod;
+mod three_d_secure;
+pub use three_d_secure::{
+    AccountInfo, AttemptAuthentication, ChallengeIndicator, MerchantRiskIndicator, MpiData,
+    ScaExemption, ThreeDSOptions,
+};
+#[cfg(feature = "tracing")]
+mod trace;
+mod validation;
+pub use validation::{FieldError, ValidationError};
+#[cfg(feature = "webhooks")]
 pub mod webhook;
+#[cfg(feature = "webhooks")]
 pub use webhook::Webhook;
 mod amount;
-pub use amount::Amount;
+pub use amount::{Amount, Rounding};
 pub use payment::RefusalReason;
 pub mod prelude {
     pub use super::{
-        action::{Action, Scheme as SchemeAction, SchemeRedirectData},
+        action::{Action, RedirectAction, ThreeDS2Action, ThreeDS2Subtype},
         browser_info::BrowserInfo,
         payment::{RefusalReason, Response},
-        Currency, Environment, Error, Gateway,
+        Currency, Error, MerchantAccount, MerchantReference, NetworkTxReference, PspReference,
+        RecurringProcessingModel, Secret, ShopperReference, StoredPaymentMethodId,
+    };
+
+    #[cfg(feature = "client")]
+    pub use super::{
+        payment_details::PaymentDetails,
+        payment_methods::{PaymentMethod, PaymentMethods},
+        sessions::{Session, SessionResult, SessionStatus, StorePaymentMethodMode},
+        store_new_card_on_file::CardVerification,
+        stored_payment_methods::StoredPaymentMethod,
+        Environment, Gateway,
     };
 }
 
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
 pub enum Environment {
-    Test { api_key: String },
-    Live { api_key: String, url_prefix: String },
+    Test { api_key: Secret },
+    Live { api_key: Secret, url_prefix: String },
 }
 
+#[cfg(feature = "client")]
 pub struct Gateway {
     client: reqwest::Client,
-    _environment: Environment,
     base_api_url: String,
+    api_key: RwLock<Secret>,
+    api_versions: ApiVersions,
+    timeout: Duration,
+    retry_policy: RetryPolicy,
+    merchant_account: Option<MerchantAccount>,
+    merchant_account_routing: Option<MerchantAccountRouting>,
+    #[cfg(feature = "tracing")]
+    log_bodies: bool,
 }
 
-pub fn convert_decimal_into_minor_units<'a>(
-    amount: &'a Decimal,
-    currency: &'a Currency,
+/// Converts a decimal amount in major units into minor units. Returns an error if the amount has
+/// more decimals than the currency allows; use `Amount::from_decimal` to round instead.
+pub fn convert_decimal_into_minor_units(
+    amount: &Decimal,
+    currency: &Currency,
 ) -> Result<u64, Error> {
-    let decimals = match currency {
-        Currency::NOK => 2,
-        Currency::SEK => 2,
-        Currency::DKK => 2,
-        Currency::ISK => 0,
-        Currency::GBP => 2,
-        Currency::EUR => 2,
-    };
+    Ok(Amount::from_decimal(amount, *currency, Rounding::Exact)?.value)
+}
+
+#[cfg(feature = "client")]
+impl Gateway {
+    pub fn new(environment: Environment, timeout: Option<Duration>) -> Result<Gateway, Error> {
+        let mut builder = GatewayBuilder::new(environment);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder.build()
+    }
 
-    // Get minor units from decimal.
-    let minor_units_modifier = Decimal::from(10i32.pow(decimals));
-    let amount = amount * minor_units_modifier;
+    pub fn builder(environment: Environment) -> GatewayBuilder {
+        GatewayBuilder::new(environment)
+    }
 
-    match amount.to_u64() {
-        Some(a) => Ok(a),
-        None => {
-            return Err(Error::ConversionError(format!(
-                "could not convert \"{}\" to u64",
-                amount
-            )))
+    /// Replaces the API key used for subsequent requests, e.g. when rotating keys in the Customer
+    /// Area. Requests already sent keep the old key.
+    pub fn rotate_api_key(&self, api_key: impl Into<Secret>) -> Result<(), Error> {
+        let api_key = api_key.into();
+        api_key_header(&api_key)?;
+
+        match self.api_key.write() {
+            Ok(mut current) => *current = api_key,
+            Err(_) => return Err(Error::Unspecified(String::from("api key lock poisoned"))),
         }
+
+        Ok(())
     }
-}
 
-impl Gateway {
-    pub fn new(environment: Environment, timeout: Option<Duration>) -> Result<Gateway, Error> {
-        let base_api_url = match &environment {
-            Environment::Test { .. } => format!("https://checkout-test.adyen.com"),
-            Environment::Live { url_prefix, .. } => {
-                format!(
-                    "https://{}-checkout-live.adyenpayments.com/checkout",
-                    url_prefix
-                )
-            }
-        };
+    fn api_key_header(&self) -> Result<reqwest::header::HeaderValue, Error> {
+        match self.api_key.read() {
+            Ok(api_key) => api_key_header(&api_key),
+            Err(_) => Err(Error::Unspecified(String::from("api key lock poisoned"))),
+        }
+    }
 
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            "Content-Type",
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            "Accept",
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-
-        let api_key = match &environment {
-            Environment::Test { api_key } | Environment::Live { api_key, .. } => api_key,
-        };
+    fn checkout_url(&self, version: u16, path: &str) -> String {
+        format!("{}/v{}{}", self.base_api_url, version, path)
+    }
 
-        let x_api_key_header_value = format!("{}", api_key);
-        let x_api_key_header = match reqwest::header::HeaderValue::from_str(&x_api_key_header_value)
+    /// Like `checkout_url`, with each of `segments` percent-encoded and appended as a single path
+    /// segment, so that an id containing e.g. "/" or "?" cannot change the endpoint.
+    fn checkout_url_with_segments(
+        &self,
+        version: u16,
+        path: &str,
+        segments: &[&str],
+    ) -> Result<String, Error> {
+        // URL parsers drop or resolve these rather than encoding them.
+        if let Some(segment) = segments
+            .iter()
+            .find(|segment| matches!(**segment, "" | "." | ".."))
         {
-            Ok(header) => header,
+            return Err(Error::Unspecified(format!(
+                "\"{}\" is not a valid path segment",
+                segment
+            )));
+        }
+
+        let mut url = match url::Url::parse(&self.checkout_url(version, path)) {
+            Ok(url) => url,
             Err(err) => {
                 return Err(Error::Unspecified(format!(
-                    "could not create auth header ({})",
-                    err.to_string()
+                    "could not create request url ({})",
+                    err
                 )))
             }
         };
 
-        headers.insert("x-API-key", x_api_key_header);
+        match url.path_segments_mut() {
+            Ok(mut path_segments) => {
+                path_segments.extend(segments);
+            }
+            Err(()) => {
+                return Err(Error::Unspecified(String::from(
+                    "could not create request url",
+                )))
+            }
+        }
 
-        let timeout = match timeout {
-            Some(t) => t,
-            None => Duration::new(60, 0),
-        };
+        Ok(url.into())
+    }
+
+    async fn post<T: DeserializeOwned>(&self, url: &str, body: impl Serialize) -> Result<T, Error> {
+        let text = self.send(self.client.post(url).json(&body)).await?;
+        parse_body(&text)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, Error> {
+        let text = self.send(self.client.get(url).query(query)).await?;
+        parse_body(&text)
+    }
+
+    async fn delete(&self, url: &str, query: &[(&str, &str)]) -> Result<(), Error> {
+        self.send(self.client.delete(url).query(query)).await?;
+        Ok(())
+    }
 
-        let client = match reqwest::ClientBuilder::new()
-            .default_headers(headers)
-            .https_only(true)
-            .timeout(timeout)
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<String, Error> {
+        let request = match request
+            .header("x-API-key", self.api_key_header()?)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .timeout(self.timeout)
             .build()
         {
             Ok(r) => r,
             Err(err) => {
                 return Err(Error::Unspecified(format!(
-                    "could not create reqwest client ({})",
-                    err.to_string()
+                    "could not build request ({})",
+                    err
                 )))
             }
         };
 
-        Ok(Gateway {
-            client,
-            _environment: environment,
-            base_api_url,
-        })
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+            let span = trace::request_span(&request, self.log_bodies);
+            self.execute(request).instrument(span).await
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        self.execute(request).await
     }
 
-    async fn post<'a, T: DeserializeOwned>(
-        &self,
-        url: &str,
-        body: impl Serialize,
-    ) -> Result<T, Error> {
-        let res = match self.client.post(url).json(&body).send().await {
+    async fn execute(&self, request: reqwest::Request) -> Result<String, Error> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
+        let mut retries = 0;
+        let res = loop {
+            let attempt = match request.try_clone() {
+                Some(r) => r,
+                None => return Err(Error::Unspecified(String::from("could not clone request"))),
+            };
+
+            let res = self.client.execute(attempt).await;
+
+            // Only requests Adyen did not process are retried, so payments are never duplicated.
+            let retryable = match &res {
+                Ok(res) => res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS,
+                Err(err) => err.is_connect(),
+            };
+            if retryable && retries < self.retry_policy.max_retries {
+                tokio::time::sleep(self.retry_policy.backoff(retries)).await;
+                retries += 1;
+                continue;
+            }
+
+            break res;
+        };
+
+        let res = match res {
             Ok(r) => r,
             Err(err) => {
+                #[cfg(feature = "tracing")]
+                trace::record_failure(&err, started.elapsed(), retries);
+
                 return Err(Error::NetworkError(format!(
                     "could not send request ({})",
-                    err.to_string()
-                )))
+                    err
+                )));
             }
         };
 
@@ -162,7 +334,11 @@ impl Gateway {
             .unwrap_or_else(|_| String::from("Could not retrieve body text."));
 
         let status = status.as_u16();
-        if status < 200 || status >= 300 {
+
+        #[cfg(feature = "tracing")]
+        trace::record_response(status, &text, started.elapsed(), retries, self.log_bodies);
+
+        if !(200..300).contains(&status) {
             #[derive(Deserialize, Debug, Clone)]
             #[serde(rename_all = "camelCase")]
             struct ApiError {
@@ -194,120 +370,71 @@ impl Gateway {
             }));
         }
 
-        let body: T = match serde_json::from_str(&text) {
-            Ok(r) => r,
-            Err(err) => {
-                return Err(Error::SerializationError(format!(
-                    "could not deserialize response ({}): {}",
-                    err, text
-                )))
-            }
-        };
-        Ok(body)
+        Ok(text)
     }
+}
+
+#[cfg(feature = "client")]
+fn api_key_header(api_key: &Secret) -> Result<reqwest::header::HeaderValue, Error> {
+    let mut header = match reqwest::header::HeaderValue::from_str(api_key.expose()) {
+        Ok(header) => header,
+        Err(err) => {
+            return Err(Error::Unspecified(format!(
+                "could not create auth header ({})",
+                err
+            )))
+        }
+    };
+    header.set_sensitive(true);
+    Ok(header)
+}
 
-    // async fn get<'a, T: DeserializeOwned>(&self, url: &str) -> Result<T, Error> {
-    //     let res = match self.client.get(url).send().await {
-    //         Ok(r) => r,
-    //         Err(err) => {
-    //             return Err(Error::NetworkError(format!(
-    //                 "could not send request ({})",
-    //                 err
-    //             )))
-    //         }
-    //     };
-
-    //     let status = res.status();
-    //     let text = res
-    //         .text()
-    //         .await
-    //         .unwrap_or_else(|_| String::from("Could not retrieve body text."));
-
-    //     if status.as_u16() < 200 || status.as_u16() >= 300 {
-    //         #[derive(Deserialize, Debug, Clone)]
-    //         #[serde(rename_all = "camelCase")]
-    //         struct ApiError {
-    //             pub meta: Option<Meta>,
-    //         }
-
-    //         let api_error: ApiError =
-    //             serde_json::from_str(&text).unwrap_or_else(|_| ApiError { meta: None });
-
-    //         let meta = match api_error.meta {
-    //             Some(m) => m,
-    //             None => {
-    //                 return Err(Error::ApiError(
-    //                     status.as_u16().to_string(),
-    //                     "unknown".to_string(),
-    //                     None,
-    //                     None,
-    //                     text,
-    //                 ))
-    //             }
-    //         };
-
-    //         let action = match meta.action {
-    //             Some(m) => m,
-    //             None => {
-    //                 return Err(Error::ApiError(
-    //                     status.as_u16().to_string(),
-    //                     "unknown".to_string(),
-    //                     None,
-    //                     None,
-    //                     text,
-    //                 ))
-    //             }
-    //         };
-
-    //         let code = match action.code {
-    //             Some(m) => m,
-    //             None => {
-    //                 return Err(Error::ApiError(
-    //                     status.as_u16().to_string(),
-    //                     "unknown".to_string(),
-    //                     None,
-    //                     None,
-    //                     text,
-    //                 ))
-    //             }
-    //         };
-
-    //         let source = match action.source {
-    //             Some(m) => m,
-    //             None => {
-    //                 return Err(Error::ApiError(
-    //                     status.as_u16().to_string(),
-    //                     "unknown".to_string(),
-    //                     None,
-    //                     None,
-    //                     text,
-    //                 ))
-    //             }
-    //         };
-
-    //         let (enduser_message, merchant_message) = match meta.message {
-    //             Some(m) => (m.enduser, m.merchant),
-    //             None => (None, None),
-    //         };
-
-    //         return Err(Error::ApiError(
-    //             code,
-    //             source,
-    //             enduser_message,
-    //             merchant_message,
-    //             text,
-    //         ));
-    //     }
-
-    //     let body: T = match serde_json::from_str(&text) {
-    //         Ok(r) => r,
-    //         Err(err) => {
-    //             return Err(Error::SerializationError(format!(
-    //                 "could not deserialize response ({}): {}",
-    //                 err, text
-    //             )))
-    //         }
-    //     };
-    //     Ok(body)
-    // }
+#[cfg(feature = "client")]
+fn parse_body<T: DeserializeOwned>(text: &str) -> Result<T, Error> {
+    match serde_json::from_str(text) {
+        Ok(r) => Ok(r),
+        Err(err) => Err(Error::SerializationError(format!(
+            "could not deserialize response ({}): {}",
+            err, text
+        ))),
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_path_segments() {
+        let gateway = Gateway::new(
+            Environment::Test {
+                api_key: Secret::from("key"),
+            },
+            None,
+        )
+        .unwrap();
+
+        let cases = [
+            ("8415", "/8415"),
+            ("a/b?c#d", "/a%2Fb%3Fc%23d"),
+            ("a b%", "/a%20b%25"),
+        ];
+        for (segment, expected) in cases {
+            assert_eq!(
+                gateway
+                    .checkout_url_with_segments(71, "/storedPaymentMethods", &[segment])
+                    .unwrap(),
+                format!(
+                    "https://checkout-test.adyen.com/v71/storedPaymentMethods{}",
+                    expected
+                )
+            );
+        }
+
+        for segment in ["", ".", ".."] {
+            assert!(gateway
+                .checkout_url_with_segments(71, "/storedPaymentMethods", &[segment])
+                .is_err());
+        }
+    }
 }
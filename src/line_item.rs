@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// An item in the shopper's order. Amounts are in minor units of the payment currency.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/sessions#request-lineItems
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LineItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub quantity: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub amount_including_tax: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub amount_excluding_tax: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub tax_amount: Option<u64>,
+
+    /// The tax percentage in minor units, e.g. 2500 for 25%.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub tax_percentage: Option<u64>,
+}
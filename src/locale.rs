@@ -0,0 +1,337 @@
+use crate::{error::Error, Amount, Currency, Rounding};
+use rust_decimal::Decimal;
+use std::{fmt, str::FromStr};
+
+const NO_BREAK_SPACE: char = '\u{a0}';
+
+/// The error returned when a string is not a supported locale.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseLocaleError(pub String);
+
+impl std::error::Error for ParseLocaleError {}
+
+impl fmt::Display for ParseLocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsupported locale \"{}\"", self.0)
+    }
+}
+
+/// A locale used to format and parse amounts.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Locale {
+    /// Norwegian Bokmål (Norway), e.g. "kr 1 234,50".
+    NbNo,
+
+    /// Swedish (Sweden), e.g. "1 234,50 kr".
+    SvSe,
+
+    /// Danish (Denmark), e.g. "1.234,50 kr.".
+    DaDk,
+
+    /// Icelandic (Iceland), e.g. "1.235 kr.".
+    IsIs,
+
+    /// English (United Kingdom), e.g. "£1,234.50".
+    EnGb,
+
+    /// English (United States), e.g. "$1,234.50".
+    EnUs,
+}
+
+impl Locale {
+    /// The BCP 47 language tag, e.g. "nb-NO".
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::NbNo => "nb-NO",
+            Locale::SvSe => "sv-SE",
+            Locale::DaDk => "da-DK",
+            Locale::IsIs => "is-IS",
+            Locale::EnGb => "en-GB",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// The currency of the locale's country.
+    pub fn currency(&self) -> Currency {
+        match self {
+            Locale::NbNo => Currency::NOK,
+            Locale::SvSe => Currency::SEK,
+            Locale::DaDk => Currency::DKK,
+            Locale::IsIs => Currency::ISK,
+            Locale::EnGb => Currency::GBP,
+            Locale::EnUs => Currency::USD,
+        }
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self {
+            Locale::NbNo | Locale::SvSe | Locale::DaDk | Locale::IsIs => ',',
+            Locale::EnGb | Locale::EnUs => '.',
+        }
+    }
+
+    fn group_separator(&self) -> char {
+        match self {
+            Locale::NbNo | Locale::SvSe => NO_BREAK_SPACE,
+            Locale::DaDk | Locale::IsIs => '.',
+            Locale::EnGb | Locale::EnUs => ',',
+        }
+    }
+
+    fn symbol_first(&self) -> bool {
+        match self {
+            Locale::NbNo | Locale::EnGb | Locale::EnUs => true,
+            Locale::SvSe | Locale::DaDk | Locale::IsIs => false,
+        }
+    }
+
+    /// The locale's own currency is shown with its local symbol; any other currency is shown
+    /// with its ISO code so amounts are never ambiguous (e.g. "kr" for both NOK and SEK).
+    fn symbol(&self, currency: Currency) -> &'static str {
+        if currency != self.currency() {
+            return currency.code();
+        }
+
+        match self {
+            Locale::DaDk | Locale::IsIs => "kr.",
+            _ => currency.symbol(),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    fn from_str(g: &str) -> Result<Self, Self::Err> {
+        match g.replace('_', "-").to_ascii_lowercase().as_str() {
+            "nb-no" | "nb" | "no-no" | "no" => Ok(Locale::NbNo),
+            "sv-se" | "sv" => Ok(Locale::SvSe),
+            "da-dk" | "da" => Ok(Locale::DaDk),
+            "is-is" | "is" => Ok(Locale::IsIs),
+            "en-gb" => Ok(Locale::EnGb),
+            "en-us" | "en" => Ok(Locale::EnUs),
+            _ => Err(ParseLocaleError(g.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+impl Amount {
+    /// Formats the amount for display in the given locale, using the locale's grouping, decimal
+    /// separator and symbol placement, and the currency's number of decimals.
+    pub fn format(&self, locale: Locale) -> String {
+        let exponent = self.currency.exponent();
+        let divisor = 10u64.pow(exponent);
+        let major: Vec<char> = (self.value / divisor).to_string().chars().collect();
+
+        // Groups of three digits, counted from the right.
+        let groups: Vec<String> = major
+            .rchunks(3)
+            .rev()
+            .map(|group| group.iter().collect())
+            .collect();
+        let mut number = groups.join(&locale.group_separator().to_string());
+
+        if exponent > 0 {
+            number.push(locale.decimal_separator());
+            number.push_str(&format!(
+                "{:0width$}",
+                self.value % divisor,
+                width = exponent as usize
+            ));
+        }
+
+        let symbol = locale.symbol(self.currency);
+        if !locale.symbol_first() {
+            return format!("{}{}{}", number, NO_BREAK_SPACE, symbol);
+        }
+
+        // Letter symbols such as "kr" and ISO codes are separated from the number.
+        match symbol.chars().all(char::is_alphabetic) {
+            true => format!("{}{}{}", symbol, NO_BREAK_SPACE, number),
+            false => format!("{}{}", symbol, number),
+        }
+    }
+
+    /// Parses an amount formatted for the given locale, e.g. "kr 1 234,50" or "1234,5". The
+    /// currency symbol or ISO code is optional. Returns an error if the input has more decimals
+    /// than the currency allows.
+    pub fn parse(input: &str, currency: Currency, locale: Locale) -> Result<Amount, Error> {
+        let mut number = input.trim();
+        for symbol in [locale.symbol(currency), currency.code(), currency.symbol()] {
+            number = number
+                .strip_prefix(symbol)
+                .or_else(|| number.strip_suffix(symbol))
+                .unwrap_or(number)
+                .trim();
+        }
+
+        let group_separator = locale.group_separator();
+        let normalized: String = number
+            .chars()
+            .filter(|c| *c != group_separator && !c.is_whitespace())
+            .map(|c| match c == locale.decimal_separator() {
+                true => '.',
+                false => c,
+            })
+            .collect();
+
+        if normalized.is_empty() || !normalized.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Err(Error::ConversionError(format!(
+                "could not parse \"{}\" as an amount in {}",
+                input, locale
+            )));
+        }
+
+        let decimal = Decimal::from_str(&normalized).map_err(|err| {
+            Error::ConversionError(format!(
+                "could not parse \"{}\" as an amount in {} ({})",
+                input, locale, err
+            ))
+        })?;
+
+        Amount::from_decimal(&decimal, currency, Rounding::Exact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCALES: [Locale; 6] = [
+        Locale::NbNo,
+        Locale::SvSe,
+        Locale::DaDk,
+        Locale::IsIs,
+        Locale::EnGb,
+        Locale::EnUs,
+    ];
+
+    #[test]
+    fn formats_in_the_locale_currency() {
+        let cases = [
+            (Locale::NbNo, 123450, "kr\u{a0}1\u{a0}234,50"),
+            (Locale::SvSe, 123450, "1\u{a0}234,50\u{a0}kr"),
+            (Locale::DaDk, 123450, "1.234,50\u{a0}kr."),
+            (Locale::IsIs, 1235, "1.235\u{a0}kr."),
+            (Locale::EnGb, 123450, "£1,234.50"),
+            (Locale::EnUs, 123450, "$1,234.50"),
+        ];
+        for (locale, value, expected) in cases {
+            let amount = Amount::new(value, locale.currency());
+            assert_eq!(amount.format(locale), expected);
+        }
+    }
+
+    #[test]
+    fn formats_other_currencies_with_their_code() {
+        assert_eq!(
+            Amount::new(123450, Currency::EUR).format(Locale::NbNo),
+            "EUR\u{a0}1\u{a0}234,50"
+        );
+        assert_eq!(
+            Amount::new(1234567, Currency::JPY).format(Locale::EnUs),
+            "JPY\u{a0}1,234,567"
+        );
+        assert_eq!(
+            Amount::new(1234567, Currency::KWD).format(Locale::SvSe),
+            "1\u{a0}234,567\u{a0}KWD"
+        );
+    }
+
+    #[test]
+    fn groups_digits_in_threes() {
+        let cases = [
+            (0, "$0.00"),
+            (5, "$0.05"),
+            (99999, "$999.99"),
+            (100000, "$1,000.00"),
+            (123456789012, "$1,234,567,890.12"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                Amount::new(value, Currency::USD).format(Locale::EnUs),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn parse_round_trips_format() {
+        let values = [0, 1, 10, 999, 1000, 123450, 1234567, 100000000, u64::MAX];
+        for locale in LOCALES {
+            for currency in [
+                locale.currency(),
+                Currency::JPY,
+                Currency::KWD,
+                Currency::EUR,
+            ] {
+                for value in values {
+                    let amount = Amount::new(value, currency);
+                    let formatted = amount.format(locale);
+                    assert_eq!(
+                        Amount::parse(&formatted, currency, locale).unwrap(),
+                        amount,
+                        "{} in {}",
+                        formatted,
+                        locale
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parses_input_without_symbol_or_grouping() {
+        let cases = [
+            (Locale::NbNo, "1234,5", 123450),
+            (Locale::NbNo, "1 234,50 kr", 123450),
+            (Locale::SvSe, "1234", 123400),
+            (Locale::DaDk, "1.234,50", 123450),
+            (Locale::IsIs, "1235", 1235),
+            (Locale::EnGb, "1,234.5", 123450),
+            (Locale::EnUs, "USD 1234.50", 123450),
+        ];
+        for (locale, input, value) in cases {
+            assert_eq!(
+                Amount::parse(input, locale.currency(), locale).unwrap(),
+                Amount::new(value, locale.currency()),
+                "{} in {}",
+                input,
+                locale
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_input() {
+        for locale in LOCALES {
+            let currency = locale.currency();
+            for input in ["", "kr", "abc", "-12", "-0", "1e3"] {
+                assert!(
+                    Amount::parse(input, currency, locale).is_err(),
+                    "{} in {}",
+                    input,
+                    locale
+                );
+            }
+        }
+
+        // More decimals than the currency allows.
+        assert!(Amount::parse("12,345", Currency::NOK, Locale::NbNo).is_err());
+        assert!(Amount::parse("1235,5", Currency::ISK, Locale::IsIs).is_err());
+    }
+
+    #[test]
+    fn parses_tags() {
+        for locale in LOCALES {
+            assert_eq!(locale.tag().parse::<Locale>().unwrap(), locale);
+        }
+        assert!("fr-FR".parse::<Locale>().is_err());
+    }
+}
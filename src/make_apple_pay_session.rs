@@ -1,4 +1,4 @@
-use crate::{amount, error::Error, Gateway};
+use crate::{amount, error::Error, validation::Validator, Gateway, MerchantAccount};
 use serde::{Deserialize, Serialize};
 
 impl Gateway {
@@ -10,61 +10,33 @@ impl Gateway {
         channel: &'a str,
         display_name: &'a str,
         domain_name: &'a str,
-        merchant_account: &'a str,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<String, Error> {
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct PaymentMethodsRequest<'a> {
-            merchant_account: &'a str,
-
-            country_code: &'a str,
-
-            amount: &'a amount::Amount,
-
-            channel: &'a str,
-        }
-
-        let body = PaymentMethodsRequest {
-            merchant_account,
-            country_code,
-            amount,
-            channel,
-        };
-
-        #[derive(Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct Configuration {
-            merchant_id: String,
-            // merchant_name: String,
-        }
-
-        #[derive(Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct PaymentMethod {
-            r#type: String,
-
-            #[serde(skip_serializing_if = "Option::is_none")]
-            #[serde(default)]
-            configuration: Option<Configuration>,
-        }
-
-        #[derive(Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct PaymentMethodsResponse {
-            payment_methods: Vec<PaymentMethod>,
-        }
-
-        let url = format!("{}/v71/paymentMethods", self.base_api_url);
-        let res: PaymentMethodsResponse = self.post(&url, &body).await?;
+        Validator::new()
+            .country_code(&Some(country_code))
+            .not_empty("displayName", display_name)
+            .not_empty("domainName", domain_name)
+            .finish()?;
+
+        let res = self
+            .payment_methods(
+                &Some(country_code),
+                &None,
+                &Some(amount),
+                &Some(channel),
+                &None,
+                &[],
+                &[],
+                merchant_account,
+            )
+            .await?;
 
         // Get merchant identifier.
         let merchant_identifier = res
-            .payment_methods
-            .iter()
-            .find(|method| method.r#type == "applepay")
+            .find("applepay")
             .and_then(|method| method.configuration.as_ref())
-            .map(|config| &config.merchant_id)
-            .ok_or_else(|| Error::UnsupportedPaymentMethod)?;
+            .and_then(|config| config.merchant_id.as_ref())
+            .ok_or(Error::UnsupportedPaymentMethod)?;
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -86,7 +58,7 @@ impl Gateway {
             pub data: String,
         }
 
-        let url = format!("{}/v71/applePay/sessions", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.utility, "/applePay/sessions");
         let res: ApplePaySession = self.post(&url, &body).await?;
 
         Ok(res.data)
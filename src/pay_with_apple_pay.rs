@@ -1,4 +1,7 @@
-use crate::{amount, error::Error, payment, Gateway};
+use crate::{
+    amount, error::Error, payment, validation::Validator, Gateway, MerchantAccount,
+    MerchantReference, RoutingContext, ShopperDetails,
+};
 use serde::Serialize;
 
 impl Gateway {
@@ -7,10 +10,26 @@ impl Gateway {
         &self,
         amount: &'a amount::Amount,
         apple_pay_token: &'a str,
-        reference: &'a str,
+        reference: &'a MerchantReference,
         return_url: &'a str,
-        merchant_account: &'a str,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        Validator::new()
+            .amount(amount.value)
+            .not_empty("applePayToken", apple_pay_token)
+            .return_url(return_url)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(amount.currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct PaymentMethod<'a> {
@@ -27,10 +46,12 @@ impl Gateway {
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: &'a amount::Amount,
-            reference: &'a str,
+            reference: &'a MerchantReference,
             payment_method: PaymentMethod<'a>,
             return_url: &'a str,
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
         }
 
         let body = Request {
@@ -38,10 +59,11 @@ impl Gateway {
             payment_method,
             reference,
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
@@ -1,19 +1,54 @@
-use crate::{currency::Currency, error::Error, payment, Gateway};
+use crate::{
+    currency::Currency, error::Error, payment, three_d_secure::ThreeDSFields,
+    validation::Validator, BrowserInfo, Gateway, Installments, MerchantAccount, MerchantReference,
+    MpiData, NetworkTxReference, RecurringProcessingModel, RoutingContext, ShopperDetails,
+    ShopperReference, StoredPaymentMethodId, ThreeDSOptions,
+};
 use serde::Serialize;
 
 impl Gateway {
     // https://docs.adyen.com/payment-methods/cards/custom-card-integration/#make-payment-with-token
     // https://docs.adyen.com/online-payments/tokenization/advanced-flow/#pay-with-a-token
+    // https://docs.adyen.com/online-payments/tokenization/merchant-initiated-transactions
+    // The browser and 3D Secure fields are for `RecurringProcessingModel::CardOnFile`, where the
+    // shopper is present and may be authenticated.
+    #[allow(clippy::too_many_arguments)]
     pub async fn pay_with_card_on_file<'a>(
         &self,
         amount: u64,
         currency: &'a Currency,
-        reference: &'a str,
-        shopper_reference: &'a str,
-        stored_payment_method_id: &'a str,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        stored_payment_method_id: &'a StoredPaymentMethodId,
+        recurring_processing_model: RecurringProcessingModel,
+        network_tx_reference: &'a Option<&'a NetworkTxReference>,
+        installments: &'a Option<&'a Installments>,
         return_url: &'a str,
-        merchant_account: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        Validator::new()
+            .amount(amount)
+            .return_url(return_url)
+            .shopper_email(shopper_email)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(*currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Amount<'a> {
@@ -30,39 +65,74 @@ impl Gateway {
         #[serde(rename_all = "camelCase")]
         struct PaymentMethod<'a> {
             r#type: &'a str,
-            stored_payment_method_id: &'a str,
+            stored_payment_method_id: &'a StoredPaymentMethodId,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            network_payment_reference: &'a Option<&'a NetworkTxReference>,
         }
 
         let payment_method = PaymentMethod {
             r#type: "scheme",
             stored_payment_method_id,
+            network_payment_reference: network_tx_reference,
         };
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: Amount<'a>,
-            reference: &'a str,
+            reference: &'a MerchantReference,
             payment_method: PaymentMethod<'a>,
-            shopper_reference: &'a str,
+            #[serde(flatten)]
+            three_d_s: Option<ThreeDSFields<'a>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            mpi_data: &'a Option<&'a MpiData>,
+            shopper_reference: &'a ShopperReference,
             shopper_interaction: &'a str,
-            recurring_processing_model: &'a str,
+            recurring_processing_model: RecurringProcessingModel,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            installments: &'a Option<&'a Installments>,
             return_url: &'a str,
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_email: &'a Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_i_p: &'a Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            channel: &'a Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            origin: &'a Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            browser_info: &'a Option<&'a BrowserInfo>,
         }
 
         let body = Request {
             amount,
             payment_method,
+            three_d_s: three_d_s.map(|options| options.fields(channel)),
+            mpi_data,
             reference,
-            shopper_reference, // Min length: 3, Max length: 256
-            shopper_interaction: "ContAuth",
-            recurring_processing_model: "UnscheduledCardOnFile",
+            shopper_reference,
+            // Only card-on-file payments are made with the shopper present; the other models
+            // are merchant-initiated.
+            shopper_interaction: match recurring_processing_model {
+                RecurringProcessingModel::CardOnFile => "Ecommerce",
+                _ => "ContAuth",
+            },
+            recurring_processing_model,
+            installments,
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
+            shopper_email,
+            shopper_i_p,
+            channel,
+            origin,
+            browser_info,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
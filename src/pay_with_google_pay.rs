@@ -1,23 +1,45 @@
-use crate::{amount, error::Error, payment, BrowserInfo, Gateway};
+use crate::{
+    amount, error::Error, payment, three_d_secure::ThreeDSFields, validation::Validator,
+    BrowserInfo, Gateway, MerchantAccount, MerchantReference, RoutingContext, ShopperDetails,
+    ShopperReference, ThreeDSOptions,
+};
 use serde::Serialize;
 
 impl Gateway {
     // https://docs.adyen.com/payment-methods/google-pay/api-only/
+    #[allow(clippy::too_many_arguments)]
     pub async fn pay_with_google_pay<'a>(
         &self,
         amount: &'a amount::Amount,
         google_pay_token: &'a str,
-        reference: &'a str,
-        shopper_reference: &'a str,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
         return_url: &'a str,
         channel: &'a Option<&'a str>,
         browser_info: &'a Option<&'a BrowserInfo>,
         shopper_email: &'a Option<&'a str>,
         shopper_i_p: &'a Option<&'a str>,
         origin: &'a Option<&'a str>,
-        three_d_s_preferred: bool,
-        merchant_account: &'a str,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        Validator::new()
+            .amount(amount.value)
+            .not_empty("googlePayToken", google_pay_token)
+            .return_url(return_url)
+            .shopper_email(shopper_email)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(amount.currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct PaymentMethod<'a> {
@@ -31,39 +53,28 @@ impl Gateway {
             google_pay_token,
         };
 
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct ThreeDSRequestData {
-            native_three_d_s: String,
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct AuthenticationData {
-            three_d_s_request_data: ThreeDSRequestData,
-
-            attempt_authentication: String,
-        }
-
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: &'a amount::Amount,
 
-            reference: &'a str,
+            reference: &'a MerchantReference,
 
             payment_method: PaymentMethod<'a>,
 
-            #[serde(skip_serializing_if = "Option::is_none")]
-            authentication_data: Option<AuthenticationData>,
+            #[serde(flatten)]
+            three_d_s: Option<ThreeDSFields<'a>>,
 
-            shopper_reference: &'a str,
+            shopper_reference: &'a ShopperReference,
 
             shopper_interaction: &'a str,
 
             return_url: &'a str,
 
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
 
             #[serde(skip_serializing_if = "Option::is_none")]
             shopper_email: &'a Option<&'a str>,
@@ -84,20 +95,13 @@ impl Gateway {
         let body = Request {
             amount,
             payment_method,
-            authentication_data: match three_d_s_preferred {
-                true => Some(AuthenticationData {
-                    three_d_s_request_data: ThreeDSRequestData {
-                        native_three_d_s: "preferred".to_string(),
-                    },
-                    attempt_authentication: "always".to_string(),
-                }),
-                false => None,
-            },
+            three_d_s: three_d_s.map(|options| options.fields(channel)),
             reference,
-            shopper_reference, // Min length: 3, Max length: 256
+            shopper_reference,
             shopper_interaction: "Ecommerce",
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
             shopper_email,
             shopper_i_p,
             channel,
@@ -105,7 +109,7 @@ impl Gateway {
             browser_info,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
@@ -1,15 +1,23 @@
-use crate::{currency::Currency, error::Error, payment, BrowserInfo, Gateway};
+use crate::{
+    currency::Currency, error::Error, payment, three_d_secure::ThreeDSFields,
+    validation::Validator, BrowserInfo, Gateway, Installments, MerchantAccount, MerchantReference,
+    MpiData, RecurringProcessingModel, RoutingContext, ShopperDetails, ShopperReference,
+    ThreeDSOptions,
+};
 use serde::Serialize;
 
 impl Gateway {
     // https://docs.adyen.com/payment-methods/cards/custom-card-integration/#make-a-payment
     // https://docs.adyen.com/development-resources/testing/3d-secure-2-authentication/#test-cards
+    #[allow(clippy::too_many_arguments)]
     pub async fn pay_with_new_card_on_file<'a>(
         &self,
         amount: u64,
         currency: &'a Currency,
-        reference: &'a str,
-        shopper_reference: &'a str,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        recurring_processing_model: RecurringProcessingModel,
+        installments: &'a Option<&'a Installments>,
         encrypted_card_number: &'a str,
         encrypted_expiry_month: &'a str,
         encrypted_expiry_year: &'a str,
@@ -21,9 +29,80 @@ impl Gateway {
         shopper_email: &'a Option<&'a str>,
         shopper_i_p: &'a Option<&'a str>,
         origin: &'a Option<&'a str>,
-        three_d_s_preferred: bool,
-        merchant_account: &'a str,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        self.new_card_payment(
+            Validator::new().amount(amount),
+            amount,
+            currency,
+            reference,
+            shopper_reference,
+            recurring_processing_model,
+            installments,
+            encrypted_card_number,
+            encrypted_expiry_month,
+            encrypted_expiry_year,
+            encrypted_security_code,
+            holder_name,
+            return_url,
+            channel,
+            browser_info,
+            shopper_email,
+            shopper_i_p,
+            origin,
+            three_d_s,
+            mpi_data,
+            shopper_details,
+            merchant_account,
+        )
+        .await
+    }
+
+    /// Sends a card payment that stores the card. Shared by `pay_with_new_card_on_file` and the
+    /// zero-value `store_new_card_on_file`, which seed `validator` with their own checks.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn new_card_payment<'a>(
+        &self,
+        validator: Validator,
+        amount: u64,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        recurring_processing_model: RecurringProcessingModel,
+        installments: &'a Option<&'a Installments>,
+        encrypted_card_number: &'a str,
+        encrypted_expiry_month: &'a str,
+        encrypted_expiry_year: &'a str,
+        encrypted_security_code: &'a str,
+        holder_name: &'a Option<&'a str>,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<payment::Response, Error> {
+        validator
+            .return_url(return_url)
+            .shopper_email(shopper_email)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(*currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Amount<'a> {
@@ -62,43 +141,38 @@ impl Gateway {
             holder_name,
         };
 
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct ThreeDSRequestData {
-            native_three_d_s: String,
-        }
-
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        struct AuthenticationData {
-            three_d_s_request_data: ThreeDSRequestData,
-
-            attempt_authentication: String,
-        }
-
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: Amount<'a>,
 
-            reference: &'a str,
+            reference: &'a MerchantReference,
 
             payment_method: PaymentMethod<'a>,
 
+            #[serde(flatten)]
+            three_d_s: Option<ThreeDSFields<'a>>,
+
             #[serde(skip_serializing_if = "Option::is_none")]
-            authentication_data: Option<AuthenticationData>,
+            mpi_data: &'a Option<&'a MpiData>,
 
-            shopper_reference: &'a str,
+            shopper_reference: &'a ShopperReference,
 
             shopper_interaction: &'a str,
 
-            recurring_processing_model: &'a str,
+            recurring_processing_model: RecurringProcessingModel,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            installments: &'a Option<&'a Installments>,
 
             store_payment_method: bool,
 
             return_url: &'a str,
 
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
 
             #[serde(skip_serializing_if = "Option::is_none")]
             shopper_email: &'a Option<&'a str>,
@@ -119,22 +193,17 @@ impl Gateway {
         let body = Request {
             amount,
             payment_method,
-            authentication_data: match three_d_s_preferred {
-                true => Some(AuthenticationData {
-                    three_d_s_request_data: ThreeDSRequestData {
-                        native_three_d_s: "preferred".to_string(),
-                    },
-                    attempt_authentication: "always".to_string(),
-                }),
-                false => None,
-            },
+            three_d_s: three_d_s.map(|options| options.fields(channel)),
+            mpi_data,
             reference,
-            shopper_reference, // Min length: 3, Max length: 256
+            shopper_reference,
             shopper_interaction: "Ecommerce",
-            recurring_processing_model: "UnscheduledCardOnFile",
+            recurring_processing_model,
+            installments,
             store_payment_method: true,
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
             shopper_email,
             shopper_i_p,
             channel,
@@ -142,7 +211,7 @@ impl Gateway {
             browser_info,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
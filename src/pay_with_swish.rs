@@ -1,4 +1,7 @@
-use crate::{currency::Currency, error::Error, payment, Gateway};
+use crate::{
+    currency::Currency, error::Error, payment, validation::Validator, Gateway, MerchantAccount,
+    MerchantReference, RoutingContext, ShopperDetails,
+};
 use serde::Serialize;
 
 impl Gateway {
@@ -7,10 +10,25 @@ impl Gateway {
         &self,
         amount: u64,
         currency: &'a Currency,
-        reference: &'a str,
+        reference: &'a MerchantReference,
         return_url: &'a str,
-        merchant_account: &'a str,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        Validator::new()
+            .amount(amount)
+            .return_url(return_url)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(*currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Amount<'a> {
@@ -35,10 +53,12 @@ impl Gateway {
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: Amount<'a>,
-            reference: &'a str,
+            reference: &'a MerchantReference,
             payment_method: PaymentMethod<'a>,
             return_url: &'a str,
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
         }
 
         let body = Request {
@@ -46,10 +66,11 @@ impl Gateway {
             payment_method,
             reference,
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
@@ -1,18 +1,37 @@
-use crate::{currency::Currency, error::Error, payment, Gateway};
+use crate::{
+    currency::Currency, error::Error, payment, validation::Validator, Gateway, MerchantAccount,
+    MerchantReference, RoutingContext, ShopperDetails,
+};
 use serde::Serialize;
 
 impl Gateway {
-    // https://docs.adyen.com/payment-methods/swish/api-only/
+    // https://docs.adyen.com/payment-methods/vipps/api-only/
+    // The shopper's phone number, if known, is taken from `shopper_details`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn pay_with_vipps<'a>(
         &self,
         amount: u64,
         currency: &'a Currency,
-        reference: &'a str,
+        reference: &'a MerchantReference,
         return_url: &'a str,
-        merchant_account: &'a str,
         channel: &'a str,
-        telephone_number: &'a Option<&'a str>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<payment::Response, Error> {
+        Validator::new()
+            .amount(amount)
+            .return_url(return_url)
+            .shopper_details(shopper_details)
+            .finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(*currency),
+                country_code: shopper_details.and_then(|details| details.country_code.as_deref()),
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Amount<'a> {
@@ -31,22 +50,25 @@ impl Gateway {
             r#type: &'a str,
 
             #[serde(skip_serializing_if = "Option::is_none")]
-            telephone_number: &'a Option<&'a str>,
+            telephone_number: Option<&'a str>,
         }
 
         let payment_method = PaymentMethod {
             r#type: "vipps",
-            telephone_number,
+            telephone_number: shopper_details
+                .and_then(|details| details.telephone_number.as_deref()),
         };
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: Amount<'a>,
-            reference: &'a str,
+            reference: &'a MerchantReference,
             payment_method: PaymentMethod<'a>,
             return_url: &'a str,
-            merchant_account: &'a str,
+            merchant_account: &'a MerchantAccount,
+            #[serde(flatten)]
+            shopper_details: &'a Option<&'a ShopperDetails>,
             channel: &'a str,
         }
 
@@ -55,11 +77,12 @@ impl Gateway {
             payment_method,
             reference,
             return_url,
-            merchant_account,
+            merchant_account: &merchant_account,
+            shopper_details,
             channel,
         };
 
-        let url = format!("{}/v71/payments", self.base_api_url);
+        let url = self.checkout_url(self.api_versions.payments, "/payments");
         let res: payment::Response = self.post(&url, &body).await?;
 
         Ok(res)
@@ -1,40 +1,385 @@
 use super::action::Action;
+use crate::{
+    Amount, MerchantReference, NetworkTxReference, PspReference, ShopperReference,
+    StoredPaymentMethodId,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Additional information about the payment. Which fields are returned depends on the payment
+/// method and on the additional data settings of the merchant account.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#responses-200-additionalData
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalData {
-    pub card_holder_name: String,
-    pub issuer_country: String,
-    pub card_summary: String,
-    pub expiry_date: String,    // The expiry date on the card (M/yyyy).
-    pub payment_method: String, // visa, mastercard, etc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub card_holder_name: Option<String>,
+
+    /// The country of the card issuer (ISO 3166-1 alpha-2).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub issuer_country: Option<String>,
+
+    /// The last four digits of the card number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub card_summary: Option<String>,
+
+    /// The expiry date on the card (M/yyyy).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expiry_date: Option<String>,
+
+    /// The payment method variant, e.g. "visa" or "mc".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_method: Option<String>,
 
     /// The Adyen alias of the card.
     /// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#responses-200-additionalData-listOfValues-alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub alias: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub alias_type: Option<String>,
 
+    /// The first six digits of the card number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub card_bin: Option<String>,
+
+    /// The first six or eight digits of the card number, as used by the issuer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub issuer_bin: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub card_issuing_bank: Option<String>,
+
+    /// The funding source of the card, e.g. "CREDIT", "DEBIT" or "PREPAID".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub funding_source: Option<String>,
+
+    /// The result of the Address Verification Service check, e.g. "4 AVS not supported for this
+    /// card type".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub avs_result: Option<String>,
+
+    /// The result of the CVC check, e.g. "1 Matches".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub cvc_result: Option<String>,
+
+    /// The authorisation code returned by the issuer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub auth_code: Option<String>,
+
+    /// The scheme's reference for the transaction, used to link merchant-initiated transactions
+    /// to the initial customer-initiated payment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub network_tx_reference: Option<NetworkTxReference>,
+
+    /// Whether the payment was authenticated with 3D Secure.
+    #[serde(with = "string_bool")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_authenticated: Option<bool>,
+
+    /// Whether liability for fraud chargebacks shifted to the issuer.
+    #[serde(with = "string_bool")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub liability_shift: Option<bool>,
+
+    /// Whether 3D Secure was offered for the card.
+    #[serde(with = "string_bool")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_offered: Option<bool>,
+
+    /// The 3D Secure version used, e.g. "2.2.0".
+    #[serde(rename = "threeDSVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_s_version: Option<String>,
+
+    /// The Electronic Commerce Indicator of the authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub eci: Option<String>,
+
+    /// The SCA exemption requested for the payment, e.g. "lowValue" or
+    /// "transactionRiskAnalysis".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub sca_exemption_requested: Option<String>,
+
     #[serde(rename = "recurring.recurringDetailReference")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    pub recurring_detail_reference: Option<String>,
+    pub recurring_detail_reference: Option<StoredPaymentMethodId>,
+
+    #[serde(rename = "recurring.shopperReference")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub recurring_shopper_reference: Option<ShopperReference>,
+
+    /// The token of the stored payment method, returned when `storePaymentMethod` was set.
+    #[serde(rename = "tokenization.storedPaymentMethodId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub tokenization_stored_payment_method_id: Option<StoredPaymentMethodId>,
+
+    /// Any additional data not covered by the fields above.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+/// Adyen returns booleans in additional data as the strings "true" and "false". Any other value,
+/// e.g. "N/A" or "", is read as `None`.
+mod string_bool {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(true) => serializer.serialize_str("true"),
+            Some(false) => serializer.serialize_str("false"),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrBool {
+            String(String),
+            Bool(bool),
+            Other(serde::de::IgnoredAny),
+        }
+
+        match Option::<StringOrBool>::deserialize(deserializer)? {
+            Some(StringOrBool::Bool(b)) => Ok(Some(b)),
+            Some(StringOrBool::String(s)) => match s.as_str() {
+                "true" => Ok(Some(true)),
+                "false" => Ok(Some(false)),
+                _ => Ok(None),
+            },
+            Some(StringOrBool::Other(_)) | None => Ok(None),
+        }
+    }
+}
+
+/// The payment method used for the payment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethod {
+    /// The card brand, e.g. "visa" or "mc".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub brand: Option<String>,
+
+    /// The payment method type, e.g. "scheme" or "swish".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub r#type: Option<String>,
 }
 
+/// The result of an individual risk check.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FraudCheckResult {
+    /// The score the check contributed to the account score.
+    pub account_score: i32,
+
+    pub check_id: i32,
+
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FraudCheckResultContainer {
+    #[serde(rename = "FraudCheckResult")]
+    pub fraud_check_result: FraudCheckResult,
+}
+
+/// The result of the risk checks performed on the payment.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#responses-200-fraudResult
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FraudResult {
+    /// The total risk score. The higher the score, the more likely the payment is fraudulent.
+    pub account_score: i32,
+
+    #[serde(default)]
+    pub results: Vec<FraudCheckResultContainer>,
+}
+
+impl FraudResult {
+    /// The individual checks, without the container Adyen wraps each of them in.
+    pub fn check_results(&self) -> impl Iterator<Item = &FraudCheckResult> {
+        self.results
+            .iter()
+            .map(|container| &container.fraud_check_result)
+    }
+}
+
+/// The order a partial payment belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_data: String,
+
+    pub psp_reference: PspReference,
+
+    pub remaining_amount: Amount,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub amount: Option<Amount>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expires_at: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+/// The result of a 3D Secure 2 authentication.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#responses-200-threeDS2Result
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreeDS2Result {
+    /// The cryptographic proof of authentication (CAVV/AAV).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub authentication_value: Option<String>,
+
+    /// The unique transaction identifier assigned by the directory server.
+    #[serde(rename = "dsTransID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub ds_trans_id: Option<String>,
+
+    /// The Electronic Commerce Indicator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub eci: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub exemption_indicator: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub message_version: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub risk_score: Option<String>,
+
+    #[serde(rename = "threeDSServerTransID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_s_server_trans_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub timestamp: Option<String>,
+
+    /// The outcome of the authentication, e.g. "Y" (authenticated) or "N" (not authenticated).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub trans_status: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub trans_status_reason: Option<String>,
+
+    /// The challenge preference sent to the issuer.
+    #[serde(rename = "threeDSRequestorChallengeInd")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_s_requestor_challenge_ind: Option<String>,
+
+    /// Why the challenge was cancelled, e.g. "01" when the shopper cancelled it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub challenge_cancel: Option<String>,
+}
+
+impl ThreeDS2Result {
+    /// Whether the shopper was authenticated ("Y") or the attempt was recorded ("A").
+    pub fn is_authenticated(&self) -> bool {
+        matches!(self.trans_status.as_deref(), Some("Y") | Some("A"))
+    }
+}
+
+/// The outcome of a payment. Only a `resultCode` this crate does not know becomes
+/// `Response::Unknown`; a known result code with an invalid field is a deserialization error.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "resultCode")]
+#[serde(remote = "Self")]
 pub enum Response {
     /// The payment has been successfully authenticated with 3D Secure 2. Returned for 3D Secure 2
     /// authentication-only transactions.
     #[serde(rename_all = "camelCase")]
-    AuthenticationFinished {},
+    AuthenticationFinished {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(rename = "threeDS2Result")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        three_d_s2_result: Option<ThreeDS2Result>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+    },
 
     /// The transaction does not require 3D Secure authentication. Returned for standalone
     /// authentication-only integrations (cf.
     /// https://docs.adyen.com/online-payments/3d-secure/other-3ds-flows/authentication-only).
     #[serde(rename_all = "camelCase")]
-    AuthenticationNotRequired {},
+    AuthenticationNotRequired {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(rename = "threeDS2Result")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        three_d_s2_result: Option<ThreeDS2Result>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+    },
 
     /// The payment was successfully authorised. This state serves as an indicator to proceed with
     /// the delivery of goods and services. This is a final state.
@@ -44,9 +389,34 @@ pub enum Response {
         #[serde(default)]
         additional_data: Option<AdditionalData>,
 
-        psp_reference: String,
+        psp_reference: PspReference,
+
+        merchant_reference: MerchantReference,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        amount: Option<Amount>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        fraud_result: Option<FraudResult>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        donation_token: Option<String>,
 
-        merchant_reference: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        order: Option<Order>,
+
+        #[serde(rename = "threeDS2Result")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        three_d_s2_result: Option<ThreeDS2Result>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
     },
 
     /// Indicates the payment has been cancelled (either by the shopper or the merchant) before
@@ -58,12 +428,43 @@ pub enum Response {
         #[serde(default)]
         refusal_reason: Option<RefusalReason>,
 
-        psp_reference: String,
+        psp_reference: PspReference,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        amount: Option<Amount>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
     },
 
     /// The issuer requires further shopper interaction before the payment can be authenticated.
     /// Returned for 3D Secure 2 transactions.
-    ChallengeShopper { action: Action },
+    #[serde(rename_all = "camelCase")]
+    ChallengeShopper {
+        action: Action,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// There was an error when the payment was being processed. The reason is given in the
     /// refusalReason field. This is a final state.
@@ -72,39 +473,163 @@ pub enum Response {
         #[serde(rename = "refusalReasonCode")]
         refusal_reason: RefusalReason,
 
-        psp_reference: String,
+        psp_reference: PspReference,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        amount: Option<Amount>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
     },
 
     /// The issuer requires the shopper's device fingerprint before the payment can be
     /// authenticated. Returned for 3D Secure 2 transactions.
     #[serde(rename_all = "camelCase")]
-    IdentifyShopper { action: Action },
+    IdentifyShopper {
+        action: Action,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// The payment has been authorised for a partial amount. This happens for card payments when
     /// the merchant supports Partial Authorisations and the cardholder has insufficient funds.
     #[serde(rename_all = "camelCase")]
-    PartiallyAuthorised {},
+    PartiallyAuthorised {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+
+        psp_reference: PspReference,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        /// The amount that was authorised.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        amount: Option<Amount>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        fraud_result: Option<FraudResult>,
+
+        /// The order to pay the remaining amount with.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        order: Option<Order>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// Indicates that it is not possible to obtain the final status of the payment. This can
     /// happen if the systems providing final status information for the payment are unavailable,
     /// or if the shopper needs to take further action to complete the payment.
     #[serde(rename_all = "camelCase")]
-    Pending { action: Action },
+    Pending {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        action: Option<Action>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// Indicates that the response contains additional information that you need to present to a
     /// shopper, so that they can use it to complete a payment.
     #[serde(rename_all = "camelCase")]
-    PresentToShopper {},
+    PresentToShopper {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        action: Option<Action>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// Indicates the payment has successfully been received by Adyen, and will be processed. This
     /// is the initial state for all payments.
     #[serde(rename_all = "camelCase")]
-    Received {},
+    Received {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        action: Option<Action>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// Indicates the shopper should be redirected to an external web page or app to complete the
     /// authorisation.
     #[serde(rename_all = "camelCase")]
-    RedirectShopper { action: Action },
+    RedirectShopper {
+        action: Action,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        psp_reference: Option<PspReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
+    },
 
     /// Indicates the payment was refused. The reason is given in the refusalReason field. This is
     /// a final state.
@@ -113,8 +638,271 @@ pub enum Response {
         #[serde(rename = "refusalReasonCode")]
         refusal_reason: RefusalReason,
 
-        psp_reference: String,
+        psp_reference: PspReference,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        merchant_reference: Option<MerchantReference>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        additional_data: Option<AdditionalData>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        amount: Option<Amount>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        fraud_result: Option<FraudResult>,
+
+        #[serde(rename = "threeDS2Result")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        three_d_s2_result: Option<ThreeDS2Result>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        payment_method: Option<PaymentMethod>,
     },
+
+    /// A result code this crate does not know. The raw response is kept as-is.
+    #[serde(untagged)]
+    #[serde(skip_deserializing)]
+    Unknown(serde_json::Value),
+}
+
+/// Reads a field of a raw payload, e.g. of `Response::Unknown`.
+fn raw_field<T: serde::de::DeserializeOwned>(value: &serde_json::Value, field: &str) -> Option<T> {
+    value
+        .get(field)
+        .and_then(|field| serde_json::from_value(field.clone()).ok())
+}
+
+const RESULT_CODES: &[&str] = &[
+    "AuthenticationFinished",
+    "AuthenticationNotRequired",
+    "Authorised",
+    "Cancelled",
+    "ChallengeShopper",
+    "Error",
+    "IdentifyShopper",
+    "PartiallyAuthorised",
+    "Pending",
+    "PresentToShopper",
+    "Received",
+    "RedirectShopper",
+    "Refused",
+];
+
+impl Serialize for Response {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Response::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Response {
+    fn deserialize<D>(deserializer: D) -> Result<Response, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        let known = value
+            .get("resultCode")
+            .and_then(serde_json::Value::as_str)
+            .map(|result_code| RESULT_CODES.contains(&result_code))
+            .unwrap_or(false);
+        if !known {
+            return Ok(Response::Unknown(value));
+        }
+
+        Response::deserialize(value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Response {
+    /// Whether the payment has reached a final state, i.e. no further action or update is
+    /// expected from this response.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Response::Authorised { .. }
+                | Response::Cancelled { .. }
+                | Response::Error { .. }
+                | Response::Refused { .. }
+        )
+    }
+
+    /// The action the shopper must complete, if any.
+    pub fn action(&self) -> Option<&Action> {
+        match self {
+            Response::ChallengeShopper { action, .. }
+            | Response::IdentifyShopper { action, .. }
+            | Response::RedirectShopper { action, .. } => Some(action),
+            Response::Pending { action, .. }
+            | Response::PresentToShopper { action, .. }
+            | Response::Received { action, .. } => action.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Adyen's unique reference for the payment, if one was returned. For `Response::Unknown` it
+    /// is read from the raw payload, which is why the value is returned owned.
+    pub fn psp_reference(&self) -> Option<PspReference> {
+        match self {
+            Response::Authorised { psp_reference, .. }
+            | Response::Cancelled { psp_reference, .. }
+            | Response::Error { psp_reference, .. }
+            | Response::PartiallyAuthorised { psp_reference, .. }
+            | Response::Refused { psp_reference, .. } => Some(psp_reference.clone()),
+            Response::AuthenticationFinished { psp_reference, .. }
+            | Response::AuthenticationNotRequired { psp_reference, .. }
+            | Response::ChallengeShopper { psp_reference, .. }
+            | Response::IdentifyShopper { psp_reference, .. }
+            | Response::Pending { psp_reference, .. }
+            | Response::PresentToShopper { psp_reference, .. }
+            | Response::Received { psp_reference, .. }
+            | Response::RedirectShopper { psp_reference, .. } => psp_reference.clone(),
+            Response::Unknown(value) => raw_field(value, "pspReference"),
+        }
+    }
+
+    /// The merchant reference of the payment, if one was returned. For `Response::Unknown` it is
+    /// read from the raw payload, which is why the value is returned owned.
+    pub fn merchant_reference(&self) -> Option<MerchantReference> {
+        match self {
+            Response::Authorised {
+                merchant_reference, ..
+            } => Some(merchant_reference.clone()),
+            Response::AuthenticationFinished {
+                merchant_reference, ..
+            }
+            | Response::AuthenticationNotRequired {
+                merchant_reference, ..
+            }
+            | Response::Cancelled {
+                merchant_reference, ..
+            }
+            | Response::ChallengeShopper {
+                merchant_reference, ..
+            }
+            | Response::Error {
+                merchant_reference, ..
+            }
+            | Response::IdentifyShopper {
+                merchant_reference, ..
+            }
+            | Response::PartiallyAuthorised {
+                merchant_reference, ..
+            }
+            | Response::Pending {
+                merchant_reference, ..
+            }
+            | Response::PresentToShopper {
+                merchant_reference, ..
+            }
+            | Response::Received {
+                merchant_reference, ..
+            }
+            | Response::RedirectShopper {
+                merchant_reference, ..
+            }
+            | Response::Refused {
+                merchant_reference, ..
+            } => merchant_reference.clone(),
+            Response::Unknown(value) => raw_field(value, "merchantReference"),
+        }
+    }
+
+    /// The result of the 3D Secure 2 authentication, if one was performed.
+    pub fn three_d_s2_result(&self) -> Option<&ThreeDS2Result> {
+        match self {
+            Response::AuthenticationFinished {
+                three_d_s2_result, ..
+            }
+            | Response::AuthenticationNotRequired {
+                three_d_s2_result, ..
+            }
+            | Response::Authorised {
+                three_d_s2_result, ..
+            }
+            | Response::Refused {
+                three_d_s2_result, ..
+            } => three_d_s2_result.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether liability for fraud chargebacks shifted to the issuer. Requires the 3D Secure
+    /// additional data to be enabled in the Customer Area.
+    pub fn liability_shift(&self) -> Option<bool> {
+        self.additional_data()
+            .and_then(|additional_data| additional_data.liability_shift)
+    }
+
+    /// The Electronic Commerce Indicator of the authentication, from the 3D Secure 2 result or
+    /// the additional data.
+    pub fn eci(&self) -> Option<&str> {
+        self.three_d_s2_result()
+            .and_then(|result| result.eci.as_deref())
+            .or_else(|| {
+                self.additional_data()
+                    .and_then(|additional_data| additional_data.eci.as_deref())
+            })
+    }
+
+    /// The card scheme's reference for the payment, to store with the card and send with later
+    /// merchant-initiated payments. Requires the network transaction reference additional data
+    /// to be enabled in the Customer Area.
+    pub fn network_tx_reference(&self) -> Option<&NetworkTxReference> {
+        self.additional_data()
+            .and_then(|additional_data| additional_data.network_tx_reference.as_ref())
+    }
+
+    /// The result of the risk checks, if the payment was scored.
+    pub fn fraud_result(&self) -> Option<&FraudResult> {
+        match self {
+            Response::Authorised { fraud_result, .. }
+            | Response::PartiallyAuthorised { fraud_result, .. }
+            | Response::Refused { fraud_result, .. } => fraud_result.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The additional data returned with the payment, if any.
+    pub fn additional_data(&self) -> Option<&AdditionalData> {
+        match self {
+            Response::AuthenticationFinished {
+                additional_data, ..
+            }
+            | Response::AuthenticationNotRequired {
+                additional_data, ..
+            }
+            | Response::Authorised {
+                additional_data, ..
+            }
+            | Response::Cancelled {
+                additional_data, ..
+            }
+            | Response::Error {
+                additional_data, ..
+            }
+            | Response::PartiallyAuthorised {
+                additional_data, ..
+            }
+            | Response::Received {
+                additional_data, ..
+            }
+            | Response::Refused {
+                additional_data, ..
+            } => additional_data.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 /// Represents various reasons why a transaction might be refused.
@@ -240,6 +1028,9 @@ pub enum RefusalReason {
 
     /// Transaction blocked by Adyen to prevent excessive retry fees.
     TransactionBlockedByAdyen,
+
+    /// A refusal reason code this crate does not know, e.g. one Adyen added later.
+    Other(String),
 }
 
 impl Serialize for RefusalReason {
@@ -288,6 +1079,7 @@ impl Serialize for RefusalReason {
             RefusalReason::CvmRequiredRestartPayment => "41",
             RefusalReason::ThreeDsAuthenticationError => "42",
             RefusalReason::TransactionBlockedByAdyen => "46",
+            RefusalReason::Other(code) => code,
         };
         serializer.serialize_str(code)
     }
@@ -352,15 +1144,7 @@ impl<'de> Deserialize<'de> for RefusalReason {
                     "41" => Ok(RefusalReason::CvmRequiredRestartPayment),
                     "42" => Ok(RefusalReason::ThreeDsAuthenticationError),
                     "46" => Ok(RefusalReason::TransactionBlockedByAdyen),
-                    _ => Err(serde::de::Error::unknown_variant(
-                        value,
-                        &[
-                            "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "14", "15",
-                            "16", "17", "18", "19", "20", "21", "22", "23", "24", "25", "26", "27",
-                            "28", "29", "31", "32", "33", "34", "35", "36", "37", "38", "39", "40",
-                            "41", "42", "46",
-                        ],
-                    )),
+                    _ => Ok(RefusalReason::Other(value.to_string())),
                 }
             }
         }
@@ -368,3 +1152,113 @@ impl<'de> Deserialize<'de> for RefusalReason {
         deserializer.deserialize_str(RefusalReasonVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_unrecognised_booleans_as_none() {
+        let response: Response = serde_json::from_value(json!({
+            "resultCode": "Authorised",
+            "pspReference": "NC6HT9CRT65ZGN82",
+            "merchantReference": "order-1",
+            "additionalData": {
+                "threeDAuthenticated": "true",
+                "threeDOffered": "N/A",
+                "liabilityShift": "",
+                "authCode": "065696"
+            }
+        }))
+        .unwrap();
+
+        match response {
+            Response::Authorised {
+                additional_data: Some(additional_data),
+                ..
+            } => {
+                assert_eq!(additional_data.three_d_authenticated, Some(true));
+                assert_eq!(additional_data.three_d_offered, None);
+                assert_eq!(additional_data.liability_shift, None);
+                assert_eq!(additional_data.auth_code.as_deref(), Some("065696"));
+            }
+            _ => panic!("expected Authorised, got {:?}", response),
+        }
+
+        let additional_data: AdditionalData = serde_json::from_value(json!({
+            "threeDAuthenticated": false,
+            "threeDOffered": "false",
+            "liabilityShift": 1
+        }))
+        .unwrap();
+        assert_eq!(additional_data.three_d_authenticated, Some(false));
+        assert_eq!(additional_data.three_d_offered, Some(false));
+        assert_eq!(additional_data.liability_shift, None);
+    }
+
+    #[test]
+    fn unknown_refusal_reason_keeps_the_result_code() {
+        let response: Response = serde_json::from_value(json!({
+            "resultCode": "Refused",
+            "refusalReasonCode": "999",
+            "pspReference": "NC6HT9CRT65ZGN82",
+        }))
+        .unwrap();
+
+        match &response {
+            Response::Refused { refusal_reason, .. } => {
+                assert_eq!(refusal_reason, &RefusalReason::Other(String::from("999")))
+            }
+            _ => panic!("expected Refused, got {:?}", response),
+        }
+        assert!(response.is_final());
+        assert_eq!(
+            serde_json::to_value(&response).unwrap()["refusalReasonCode"],
+            "999"
+        );
+    }
+
+    #[test]
+    fn unknown_result_code_is_kept_raw() {
+        let raw = json!({
+            "resultCode": "SomethingNew",
+            "pspReference": "NC6HT9CRT65ZGN82",
+        });
+        let response: Response = serde_json::from_value(raw.clone()).unwrap();
+
+        assert!(matches!(&response, Response::Unknown(value) if value == &raw));
+        assert!(!response.is_final());
+        assert_eq!(
+            response.psp_reference().unwrap().as_str(),
+            "NC6HT9CRT65ZGN82"
+        );
+        assert!(response.merchant_reference().is_none());
+        assert_eq!(serde_json::to_value(&response).unwrap(), raw);
+    }
+
+    #[test]
+    fn known_result_code_with_invalid_field_is_an_error() {
+        let response = serde_json::from_value::<Response>(json!({
+            "resultCode": "Authorised",
+            "pspReference": "NC6HT9CRT65ZGN82",
+            "merchantReference": "order-1",
+            "amount": { "value": "ten", "currency": "NOK" },
+        }));
+
+        assert!(response.is_err());
+    }
+
+    #[test]
+    fn known_result_code_round_trips() {
+        let raw = json!({
+            "resultCode": "Authorised",
+            "pspReference": "NC6HT9CRT65ZGN82",
+            "merchantReference": "order-1",
+        });
+        let response: Response = serde_json::from_value(raw.clone()).unwrap();
+
+        assert!(matches!(response, Response::Authorised { .. }));
+        assert_eq!(serde_json::to_value(&response).unwrap(), raw);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{error::Error, payment, Gateway};
+use serde::Serialize;
+
+/// The details to submit to `/payments/details` after the shopper has completed an action.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments/details#request-details
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum PaymentDetails {
+    /// The result of a native 3D Secure 2 authentication performed by Adyen's web components.
+    #[serde(rename_all = "camelCase")]
+    ThreeDSResult { three_d_s_result: String },
+
+    /// The `redirectResult` query parameter appended to the return URL after a redirect.
+    #[serde(rename_all = "camelCase")]
+    RedirectResult { redirect_result: String },
+
+    /// The `payload` query parameter appended to the return URL by some redirect payment
+    /// methods.
+    #[serde(rename_all = "camelCase")]
+    Payload { payload: String },
+
+    /// The `MD` and `PaRes` values posted back by the issuer in the legacy 3D Secure 1 flow.
+    #[serde(rename_all = "PascalCase")]
+    Legacy3DS {
+        #[serde(rename = "MD")]
+        md: String,
+
+        pa_res: String,
+    },
+
+    /// The device fingerprint produced by a native 3D Secure 2 SDK.
+    ThreeDS2Fingerprint {
+        #[serde(rename = "threeds2.fingerprint")]
+        fingerprint: String,
+    },
+
+    /// The challenge result produced by a native 3D Secure 2 SDK.
+    ThreeDS2ChallengeResult {
+        #[serde(rename = "threeds2.challengeResult")]
+        challenge_result: String,
+    },
+
+    /// Any other details, sent as-is.
+    Other(serde_json::Map<String, serde_json::Value>),
+}
+
+impl Gateway {
+    // https://docs.adyen.com/online-payments/build-your-integration/advanced-flow/#send-additional-payment-details
+    pub async fn payment_details<'a>(
+        &self,
+        details: &'a PaymentDetails,
+        payment_data: &'a Option<&'a str>,
+        three_d_s_authentication_only: bool,
+    ) -> Result<payment::Response, Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Request<'a> {
+            details: &'a PaymentDetails,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            payment_data: &'a Option<&'a str>,
+
+            #[serde(rename = "threeDSAuthenticationOnly")]
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            three_d_s_authentication_only: bool,
+        }
+
+        let body = Request {
+            details,
+            payment_data,
+            three_d_s_authentication_only,
+        };
+
+        let url = self.checkout_url(self.api_versions.payments, "/payments/details");
+        let res: payment::Response = self.post(&url, &body).await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    amount, error::Error, validation::Validator, Gateway, MerchantAccount, RoutingContext,
+    ShopperReference, StoredPaymentMethod,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An issuer (bank) available for a payment method, e.g. for iDEAL or online banking.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Issuer {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// Payment method specific configuration, e.g. the Apple Pay merchant identifier or the Google
+/// Pay gateway merchant ID.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Configuration {
+    /// The Apple Pay merchant identifier, or the Google Pay merchant ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub merchant_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub merchant_name: Option<String>,
+
+    /// The Google Pay gateway merchant ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub gateway_merchant_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub intent: Option<String>,
+
+    /// Any configuration values not covered by the fields above.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+/// A payment method available to the shopper.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/paymentMethods#responses-200-paymentMethods
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethod {
+    /// The type of payment method, e.g. "scheme", "applepay" or "vipps".
+    pub r#type: String,
+
+    /// The display name of the payment method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The brand of a single-brand payment method, e.g. a gift card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub brand: Option<String>,
+
+    /// The card brands supported by the payment method, e.g. "visa" and "mc".
+    #[serde(default)]
+    pub brands: Vec<String>,
+
+    #[serde(default)]
+    pub issuers: Vec<Issuer>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub configuration: Option<Configuration>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethods {
+    #[serde(default)]
+    pub payment_methods: Vec<PaymentMethod>,
+
+    /// The payment methods stored for the shopper. Only returned when a shopper reference is
+    /// provided.
+    #[serde(default)]
+    pub stored_payment_methods: Vec<StoredPaymentMethod>,
+}
+
+impl PaymentMethods {
+    /// Returns the first payment method of the given type, e.g. "applepay".
+    pub fn find(&self, r#type: &str) -> Option<&PaymentMethod> {
+        self.payment_methods
+            .iter()
+            .find(|method| method.r#type == r#type)
+    }
+}
+
+impl Gateway {
+    // https://docs.adyen.com/online-payments/build-your-integration/advanced-flow/#web-advanced-flow-get-payment-methods
+    #[allow(clippy::too_many_arguments)]
+    pub async fn payment_methods<'a>(
+        &self,
+        country_code: &'a Option<&'a str>,
+        shopper_locale: &'a Option<&'a str>,
+        amount: &'a Option<&'a amount::Amount>,
+        channel: &'a Option<&'a str>,
+        shopper_reference: &'a Option<&'a ShopperReference>,
+        allowed_payment_methods: &'a [&'a str],
+        blocked_payment_methods: &'a [&'a str],
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<PaymentMethods, Error> {
+        let validator = Validator::new().country_code(country_code);
+        validator.finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: amount.map(|amount| amount.currency),
+                country_code: *country_code,
+            },
+        )?;
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Request<'a> {
+            merchant_account: &'a MerchantAccount,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            country_code: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_locale: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            amount: &'a Option<&'a amount::Amount>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            channel: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_reference: &'a Option<&'a ShopperReference>,
+
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            allowed_payment_methods: &'a [&'a str],
+
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            blocked_payment_methods: &'a [&'a str],
+        }
+
+        let body = Request {
+            merchant_account: &merchant_account,
+            country_code,
+            shopper_locale,
+            amount,
+            channel,
+            shopper_reference,
+            allowed_payment_methods,
+            blocked_payment_methods,
+        };
+
+        let url = self.checkout_url(self.api_versions.payment_methods, "/paymentMethods");
+        let res: PaymentMethods = self.post(&url, &body).await?;
+
+        Ok(res)
+    }
+}
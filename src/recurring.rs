@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of agreement a stored card is used under. It sets the stored-credential indicator
+/// sent to the card scheme, so the initial payment that stores the card and the payments that
+/// follow must use the same model.
+/// https://docs.adyen.com/online-payments/tokenization/#recurring-payment-types
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RecurringProcessingModel {
+    /// Payments the shopper starts with their stored card, e.g. a one-click checkout. The
+    /// shopper is present, so these are not merchant-initiated.
+    CardOnFile,
+
+    /// Charges of a fixed or variable amount on a fixed schedule, e.g. a monthly subscription.
+    /// Installment plans also use this model, with `Installments` sent on every payment.
+    Subscription,
+
+    /// Charges without a fixed schedule, e.g. an automatic top-up when a balance runs low.
+    UnscheduledCardOnFile,
+}
+
+/// The installment plan a payment belongs to. Sending it marks the payment as an installment to
+/// the card scheme, rather than as a plain subscription charge.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#request-installments
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Installments {
+    /// The total number of installments, e.g. 6.
+    pub value: u32,
+
+    /// The kind of installment plan, e.g. "regular" or "revolving". Only used in some countries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub plan: Option<String>,
+}
@@ -1,4 +1,7 @@
-use crate::{currency::Currency, error::Error, Gateway};
+use crate::{
+    currency::Currency, error::Error, validation::Validator, Gateway, MerchantAccount,
+    MerchantReference, PspReference, RoutingContext,
+};
 use serde::{Deserialize, Serialize};
 
 impl Gateway {
@@ -7,10 +10,20 @@ impl Gateway {
         &self,
         amount: u64,
         currency: &'a Currency,
-        reference: &'a str,
-        psp_reference: &'a str,
-        merchant_account: &'a str,
+        reference: &'a MerchantReference,
+        psp_reference: &'a PspReference,
+        merchant_account: &'a Option<&'a MerchantAccount>,
     ) -> Result<(), Error> {
+        Validator::new().amount(amount).finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(*currency),
+                country_code: None,
+            },
+        )?;
+
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Amount<'a> {
@@ -27,24 +40,25 @@ impl Gateway {
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             amount: Amount<'a>,
-            reference: &'a str,
-            merchant_account: &'a str,
+            reference: &'a MerchantReference,
+            merchant_account: &'a MerchantAccount,
         }
 
         let body = Request {
             amount,
             reference,
-            merchant_account,
+            merchant_account: &merchant_account,
         };
 
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct Response {}
 
-        let url = format!(
-            "{}/v71/payments/{}/refunds",
-            self.base_api_url, psp_reference
-        );
+        let url = self.checkout_url_with_segments(
+            self.api_versions.modifications,
+            "/payments",
+            &[psp_reference.as_str(), "refunds"],
+        )?;
         let _res: Response = self.post(&url, &body).await?;
 
         Ok(())
@@ -0,0 +1,225 @@
+use crate::{error::Error, Currency, Gateway, MerchantAccount};
+use std::collections::HashMap;
+
+/// What is known about a request when its merchant account is routed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoutingContext<'a> {
+    pub currency: Option<Currency>,
+
+    /// The ISO 3166-1 alpha-2 country code of the shopper, e.g. "NO".
+    pub country_code: Option<&'a str>,
+}
+
+type Route = dyn Fn(&RoutingContext) -> Option<MerchantAccount> + Send + Sync;
+
+/// Picks the merchant account for a request when none is passed to the `Gateway` method.
+pub enum MerchantAccountRouting {
+    ByCurrency(HashMap<Currency, MerchantAccount>),
+
+    /// Keyed by ISO 3166-1 alpha-2 country code, e.g. "NO".
+    ByCountry(HashMap<String, MerchantAccount>),
+
+    Custom(Box<Route>),
+}
+
+impl MerchantAccountRouting {
+    pub fn by_currency(
+        accounts: impl IntoIterator<Item = (Currency, MerchantAccount)>,
+    ) -> MerchantAccountRouting {
+        MerchantAccountRouting::ByCurrency(accounts.into_iter().collect())
+    }
+
+    pub fn by_country<S: Into<String>>(
+        accounts: impl IntoIterator<Item = (S, MerchantAccount)>,
+    ) -> MerchantAccountRouting {
+        MerchantAccountRouting::ByCountry(
+            accounts
+                .into_iter()
+                .map(|(country_code, account)| (country_code.into().to_uppercase(), account))
+                .collect(),
+        )
+    }
+
+    pub fn custom(
+        route: impl Fn(&RoutingContext) -> Option<MerchantAccount> + Send + Sync + 'static,
+    ) -> MerchantAccountRouting {
+        MerchantAccountRouting::Custom(Box::new(route))
+    }
+
+    /// Returns `None` if the rule has no account for the context.
+    pub fn route(&self, context: &RoutingContext) -> Option<MerchantAccount> {
+        match self {
+            MerchantAccountRouting::ByCurrency(accounts) => context
+                .currency
+                .and_then(|currency| accounts.get(&currency))
+                .cloned(),
+            MerchantAccountRouting::ByCountry(accounts) => context
+                .country_code
+                .and_then(|country_code| accounts.get(&country_code.to_uppercase()))
+                .cloned(),
+            MerchantAccountRouting::Custom(route) => route(context),
+        }
+    }
+}
+
+impl Gateway {
+    /// Resolves the merchant account for a request: the one passed to the method, else the
+    /// routed one, else the default one.
+    pub(crate) fn merchant_account_for(
+        &self,
+        merchant_account: &Option<&MerchantAccount>,
+        context: RoutingContext,
+    ) -> Result<MerchantAccount, Error> {
+        if let Some(merchant_account) = merchant_account {
+            return Ok((*merchant_account).clone());
+        }
+
+        self.merchant_account_routing
+            .as_ref()
+            .and_then(|routing| routing.route(&context))
+            .or_else(|| self.merchant_account.clone())
+            .ok_or(Error::MissingMerchantAccount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Environment, GatewayBuilder};
+
+    fn account(g: &str) -> MerchantAccount {
+        MerchantAccount::new(g).unwrap()
+    }
+
+    fn build_gateway(
+        merchant_account: Option<&str>,
+        routing: Option<MerchantAccountRouting>,
+    ) -> Gateway {
+        let mut builder = GatewayBuilder::new(Environment::Test {
+            api_key: crate::Secret::from("api-key"),
+        });
+        if let Some(merchant_account) = merchant_account {
+            builder = builder.merchant_account(account(merchant_account));
+        }
+        if let Some(routing) = routing {
+            builder = builder.merchant_account_routing(routing);
+        }
+        builder.build().unwrap()
+    }
+
+    fn resolve(
+        gateway: &Gateway,
+        merchant_account: Option<&str>,
+        currency: Option<Currency>,
+        country_code: Option<&str>,
+    ) -> Result<String, Error> {
+        let merchant_account = merchant_account.map(account);
+        gateway
+            .merchant_account_for(
+                &merchant_account.as_ref(),
+                RoutingContext {
+                    currency,
+                    country_code,
+                },
+            )
+            .map(|merchant_account| merchant_account.as_str().to_string())
+    }
+
+    #[test]
+    fn routes_by_currency() {
+        let gateway = build_gateway(
+            Some("Default"),
+            Some(MerchantAccountRouting::by_currency([
+                (Currency::NOK, account("ShopNO")),
+                (Currency::SEK, account("ShopSE")),
+            ])),
+        );
+
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::SEK), Some("NO")).unwrap(),
+            "ShopSE"
+        );
+        // The default account is used when the rule has no account for the context.
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::EUR), None).unwrap(),
+            "Default"
+        );
+        assert_eq!(resolve(&gateway, None, None, None).unwrap(), "Default");
+        // An account passed to the method wins over the rule.
+        assert_eq!(
+            resolve(&gateway, Some("Passed"), Some(Currency::SEK), None).unwrap(),
+            "Passed"
+        );
+    }
+
+    #[test]
+    fn routes_by_country() {
+        let gateway = build_gateway(
+            Some("Default"),
+            Some(MerchantAccountRouting::by_country([
+                ("no", account("ShopNO")),
+                ("SE", account("ShopSE")),
+            ])),
+        );
+
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::SEK), Some("NO")).unwrap(),
+            "ShopNO"
+        );
+        assert_eq!(resolve(&gateway, None, None, Some("se")).unwrap(), "ShopSE");
+        assert_eq!(
+            resolve(&gateway, None, None, Some("DK")).unwrap(),
+            "Default"
+        );
+        assert_eq!(
+            resolve(&gateway, Some("Passed"), None, Some("NO")).unwrap(),
+            "Passed"
+        );
+    }
+
+    #[test]
+    fn routes_with_custom_rule() {
+        let gateway = build_gateway(
+            None,
+            Some(MerchantAccountRouting::custom(|context| {
+                match (context.currency, context.country_code) {
+                    (Some(Currency::EUR), Some("FI")) => Some(account("ShopFI")),
+                    (Some(Currency::EUR), _) => Some(account("ShopEU")),
+                    _ => None,
+                }
+            })),
+        );
+
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::EUR), Some("FI")).unwrap(),
+            "ShopFI"
+        );
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::EUR), Some("DE")).unwrap(),
+            "ShopEU"
+        );
+        assert!(matches!(
+            resolve(&gateway, None, Some(Currency::NOK), Some("NO")),
+            Err(Error::MissingMerchantAccount)
+        ));
+    }
+
+    #[test]
+    fn requires_a_merchant_account() {
+        let gateway = build_gateway(None, None);
+        assert!(matches!(
+            resolve(&gateway, None, Some(Currency::NOK), Some("NO")),
+            Err(Error::MissingMerchantAccount)
+        ));
+        assert_eq!(
+            resolve(&gateway, Some("Passed"), None, None).unwrap(),
+            "Passed"
+        );
+
+        let gateway = build_gateway(Some("Default"), None);
+        assert_eq!(
+            resolve(&gateway, None, Some(Currency::NOK), Some("NO")).unwrap(),
+            "Default"
+        );
+    }
+}
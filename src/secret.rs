@@ -0,0 +1,64 @@
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// A credential such as an API key, a webhook HMAC key, a basic-auth password or a Terminal API
+/// key. The value is overwritten when dropped and never shown by `Debug` or `Display`; use
+/// `expose` where the value itself is needed. It can be deserialized but, to keep it out of
+/// serialized output, not serialized. Nor can it be compared with `==`, which would take a time
+/// that depends on the value.
+#[derive(Clone)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Secret {
+        Secret(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret([redacted])")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[redacted]")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(g: String) -> Self {
+        Secret(g)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(g: &str) -> Self {
+        Secret(String::from(g))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> Result<Secret, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Secret)
+    }
+}
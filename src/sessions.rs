@@ -0,0 +1,215 @@
+use crate::{
+    amount, error::Error, validation::Validator, Gateway, LineItem, MerchantAccount,
+    MerchantReference, RoutingContext, ShopperReference,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Whether the payment details entered in Drop-in should be stored for future payments.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum StorePaymentMethodMode {
+    /// The payment details are never stored.
+    Disabled,
+
+    /// The shopper can choose whether to store the payment details.
+    AskForConsent,
+
+    /// The payment details are always stored.
+    Enabled,
+}
+
+/// A payment session to pass to Drop-in or Components.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// The unique identifier of the session.
+    pub id: String,
+
+    /// The payment session data to pass to the client.
+    pub session_data: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// The status of a payment session.
+/// https://docs.adyen.com/online-payments/build-your-integration/sessions-flow/#get-the-result-of-the-payment-session
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionStatus {
+    /// The session is active and can be paid.
+    Active,
+
+    /// The shopper completed the payment. The payment is authorised in most cases; the webhook
+    /// has the final outcome.
+    Completed,
+
+    /// The shopper has completed the payment, but the final result is not yet known.
+    PaymentPending,
+
+    /// The session has been refused, e.g. due to fraud.
+    Refused,
+
+    /// The shopper cancelled the payment.
+    Canceled,
+
+    /// The session expired before a payment was made.
+    Expired,
+
+    /// A status this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionResult {
+    pub id: String,
+
+    pub status: SessionStatus,
+}
+
+impl Gateway {
+    // https://docs.adyen.com/online-payments/build-your-integration/sessions-flow/#create-a-payment-session
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_session<'a>(
+        &self,
+        amount: &'a amount::Amount,
+        reference: &'a MerchantReference,
+        return_url: &'a str,
+        expires_at: &'a Option<DateTime<Utc>>,
+        country_code: &'a Option<&'a str>,
+        shopper_locale: &'a Option<&'a str>,
+        shopper_reference: &'a Option<&'a ShopperReference>,
+        shopper_email: &'a Option<&'a str>,
+        channel: &'a Option<&'a str>,
+        allowed_payment_methods: &'a [&'a str],
+        store_payment_method_mode: StorePaymentMethodMode,
+        line_items: &'a [LineItem],
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<Session, Error> {
+        let validator = Validator::new()
+            .amount(amount.value)
+            .return_url(return_url)
+            .country_code(country_code)
+            .shopper_email(shopper_email);
+        validator.finish()?;
+
+        let merchant_account = self.merchant_account_for(
+            merchant_account,
+            RoutingContext {
+                currency: Some(amount.currency),
+                country_code: *country_code,
+            },
+        )?;
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Request<'a> {
+            amount: &'a amount::Amount,
+
+            reference: &'a MerchantReference,
+
+            return_url: &'a str,
+
+            merchant_account: &'a MerchantAccount,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            expires_at: &'a Option<DateTime<Utc>>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            country_code: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_locale: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_reference: &'a Option<&'a ShopperReference>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_email: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            channel: &'a Option<&'a str>,
+
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            allowed_payment_methods: &'a [&'a str],
+
+            store_payment_method_mode: StorePaymentMethodMode,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shopper_interaction: Option<&'a str>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            recurring_processing_model: Option<&'a str>,
+
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            line_items: &'a [LineItem],
+        }
+
+        // Stored details must be usable with `pay_with_card_on_file`.
+        let (shopper_interaction, recurring_processing_model) = match store_payment_method_mode {
+            StorePaymentMethodMode::Disabled => (None, None),
+            StorePaymentMethodMode::AskForConsent | StorePaymentMethodMode::Enabled => {
+                (Some("Ecommerce"), Some("UnscheduledCardOnFile"))
+            }
+        };
+
+        let body = Request {
+            amount,
+            reference,
+            return_url,
+            merchant_account: &merchant_account,
+            expires_at,
+            country_code,
+            shopper_locale,
+            shopper_reference,
+            shopper_email,
+            channel,
+            allowed_payment_methods,
+            store_payment_method_mode,
+            shopper_interaction,
+            recurring_processing_model,
+            line_items,
+        };
+
+        let url = self.checkout_url(self.api_versions.sessions, "/sessions");
+        let res: Session = self.post(&url, &body).await?;
+
+        Ok(res)
+    }
+
+    // https://docs.adyen.com/online-payments/build-your-integration/sessions-flow/#get-the-result-of-the-payment-session
+    pub async fn session_result(
+        &self,
+        session_id: &str,
+        session_result: &str,
+    ) -> Result<SessionResult, Error> {
+        let url = self.checkout_url_with_segments(
+            self.api_versions.sessions,
+            "/sessions",
+            &[session_id],
+        )?;
+        let res: SessionResult = self.get(&url, &[("sessionResult", session_result)]).await?;
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_session_status() {
+        let res: SessionResult =
+            serde_json::from_str(r#"{"id":"CS1","status":"paymentPending"}"#).unwrap();
+        assert_eq!(res.status, SessionStatus::PaymentPending);
+
+        let res: SessionResult =
+            serde_json::from_str(r#"{"id":"CS1","status":"somethingNew"}"#).unwrap();
+        assert_eq!(res.status, SessionStatus::Unknown);
+    }
+}
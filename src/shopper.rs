@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A postal address. Adyen requires every field except `state_or_province` when an address is
+/// sent.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#request-billingAddress
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub street: String,
+
+    /// The house number or name, e.g. "12" or "Flat 3".
+    pub house_number_or_name: String,
+
+    pub postal_code: String,
+
+    pub city: String,
+
+    /// The state or province, required for addresses in the US and Canada.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub state_or_province: Option<String>,
+
+    /// The country (ISO 3166-1 alpha-2), e.g. "NO".
+    pub country: String,
+}
+
+/// The shopper's name.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Name {
+    pub first_name: String,
+
+    pub last_name: String,
+}
+
+/// Data used by RevenueProtect to score the payment.
+/// https://docs.adyen.com/risk-management/configure-custom-risk-rules
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskData {
+    /// The device data collected by the Adyen client library.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub client_data: Option<String>,
+
+    /// Values for the custom risk fields configured in the Customer Area, by field name.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    pub custom_fields: HashMap<String, String>,
+
+    /// Added to the account score of the payment, e.g. to score trusted shoppers lower.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub fraud_offset: Option<i32>,
+
+    /// The risk profile to score the payment with instead of the merchant account's default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub profile_reference: Option<String>,
+}
+
+/// Optional information about the shopper. The more is sent, the more 3D Secure 2
+/// authentications are frictionless and the better risk checks can score the payment.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShopperDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub shopper_name: Option<Name>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub billing_address: Option<Address>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_address: Option<Address>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub telephone_number: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub date_of_birth: Option<NaiveDate>,
+
+    /// The shopper's country (ISO 3166-1 alpha-2), e.g. "NO". Also used to route the payment
+    /// with `MerchantAccountRouting::ByCountry`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub country_code: Option<String>,
+
+    /// The shopper's language and country, e.g. "nb-NO".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub shopper_locale: Option<String>,
+
+    /// The device fingerprint collected by the Adyen client library.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub device_fingerprint: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub risk_data: Option<RiskData>,
+}
@@ -0,0 +1,150 @@
+use crate::{
+    action::Action, currency::Currency, error::Error, payment, validation::Validator, BrowserInfo,
+    Gateway, MerchantAccount, MerchantReference, MpiData, NetworkTxReference, PspReference,
+    RecurringProcessingModel, ShopperDetails, ShopperReference, StoredPaymentMethodId,
+    ThreeDSOptions,
+};
+use std::convert::TryFrom;
+
+/// The outcome of a zero-value card verification.
+#[derive(Debug, Clone)]
+pub enum CardVerification {
+    /// The card was verified and stored. Use `stored_payment_method_id` with
+    /// `pay_with_card_on_file` to charge the card later, along with `network_tx_reference` for
+    /// merchant-initiated payments.
+    Stored {
+        stored_payment_method_id: StoredPaymentMethodId,
+
+        psp_reference: PspReference,
+
+        network_tx_reference: Option<NetworkTxReference>,
+    },
+
+    /// The shopper must complete an action (typically 3D Secure) before the card can be stored.
+    /// Pass the action to the client, submit the result with `set_payment_details` or
+    /// `set_redirect_result`, and convert the returned response with `CardVerification::try_from`.
+    ActionRequired { action: Action },
+}
+
+impl TryFrom<payment::Response> for CardVerification {
+    type Error = Error;
+
+    fn try_from(response: payment::Response) -> Result<Self, Self::Error> {
+        match response {
+            payment::Response::Authorised {
+                additional_data,
+                psp_reference,
+                ..
+            } => {
+                let (stored_payment_method_id, network_tx_reference) = match additional_data {
+                    Some(data) => (
+                        data.tokenization_stored_payment_method_id
+                            .or(data.recurring_detail_reference),
+                        data.network_tx_reference,
+                    ),
+                    None => (None, None),
+                };
+
+                match stored_payment_method_id {
+                    Some(stored_payment_method_id) => Ok(CardVerification::Stored {
+                        stored_payment_method_id,
+                        psp_reference,
+                        network_tx_reference,
+                    }),
+                    None => Err(Error::CardNotTokenised {
+                        refusal_reason: None,
+                        psp_reference: Some(psp_reference),
+                    }),
+                }
+            }
+            payment::Response::ChallengeShopper { action, .. }
+            | payment::Response::IdentifyShopper { action, .. }
+            | payment::Response::RedirectShopper { action, .. } => {
+                Ok(CardVerification::ActionRequired { action })
+            }
+            payment::Response::Refused {
+                refusal_reason,
+                psp_reference,
+                ..
+            }
+            | payment::Response::Error {
+                refusal_reason,
+                psp_reference,
+                ..
+            } => Err(Error::CardNotTokenised {
+                refusal_reason: Some(refusal_reason),
+                psp_reference: Some(psp_reference),
+            }),
+            payment::Response::Cancelled {
+                refusal_reason,
+                psp_reference,
+                ..
+            } => Err(Error::CardNotTokenised {
+                refusal_reason,
+                psp_reference: Some(psp_reference),
+            }),
+            _ => Err(Error::CardNotTokenised {
+                refusal_reason: None,
+                psp_reference: None,
+            }),
+        }
+    }
+}
+
+impl Gateway {
+    // https://docs.adyen.com/get-started-with-adyen/payment-glossary/#zero-value-auth
+    // https://docs.adyen.com/online-payments/tokenization/create-and-use-tokens/#create-a-token
+    #[allow(clippy::too_many_arguments)]
+    pub async fn store_new_card_on_file<'a>(
+        &self,
+        currency: &'a Currency,
+        reference: &'a MerchantReference,
+        shopper_reference: &'a ShopperReference,
+        recurring_processing_model: RecurringProcessingModel,
+        encrypted_card_number: &'a str,
+        encrypted_expiry_month: &'a str,
+        encrypted_expiry_year: &'a str,
+        encrypted_security_code: &'a str,
+        holder_name: &'a Option<&'a str>,
+        return_url: &'a str,
+        channel: &'a Option<&'a str>,
+        browser_info: &'a Option<&'a BrowserInfo>,
+        shopper_email: &'a Option<&'a str>,
+        shopper_i_p: &'a Option<&'a str>,
+        origin: &'a Option<&'a str>,
+        three_d_s: &'a Option<&'a ThreeDSOptions>,
+        mpi_data: &'a Option<&'a MpiData>,
+        shopper_details: &'a Option<&'a ShopperDetails>,
+        merchant_account: &'a Option<&'a MerchantAccount>,
+    ) -> Result<CardVerification, Error> {
+        // A zero-value authorisation verifies the card without charging the shopper.
+        let res = self
+            .new_card_payment(
+                Validator::new(),
+                0,
+                currency,
+                reference,
+                shopper_reference,
+                recurring_processing_model,
+                &None,
+                encrypted_card_number,
+                encrypted_expiry_month,
+                encrypted_expiry_year,
+                encrypted_security_code,
+                holder_name,
+                return_url,
+                channel,
+                browser_info,
+                shopper_email,
+                shopper_i_p,
+                origin,
+                three_d_s,
+                mpi_data,
+                shopper_details,
+                merchant_account,
+            )
+            .await?;
+
+        CardVerification::try_from(res)
+    }
+}
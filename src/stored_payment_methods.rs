@@ -0,0 +1,118 @@
+use crate::{
+    error::Error, Gateway, MerchantAccount, RoutingContext, ShopperReference, StoredPaymentMethodId,
+};
+use serde::{Deserialize, Serialize};
+
+/// A payment method stored for a shopper, such as a card saved with `pay_with_new_card_on_file`
+/// or `store_new_card_on_file`.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/get/storedPaymentMethods#responses-200-storedPaymentMethods
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredPaymentMethod {
+    /// The token of the stored payment method (the `storedPaymentMethodId`).
+    pub id: StoredPaymentMethodId,
+
+    /// The type of payment method, e.g. "scheme".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub r#type: Option<String>,
+
+    /// The brand of the card, e.g. "visa" or "mc".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub brand: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub last_four: Option<String>,
+
+    /// The month the card expires (MM).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expiry_month: Option<String>,
+
+    /// The year the card expires (YYYY).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expiry_year: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub holder_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub shopper_email: Option<String>,
+
+    /// The shopper interactions the token can be used with, e.g. "Ecommerce" and "ContAuth".
+    #[serde(default)]
+    pub supported_shopper_interactions: Vec<String>,
+
+    /// The recurring processing models the token can be used with, e.g. "CardOnFile".
+    #[serde(default)]
+    pub supported_recurring_processing_models: Vec<String>,
+}
+
+impl Gateway {
+    // https://docs.adyen.com/online-payments/tokenization/managing-tokens/#list-saved-details
+    pub async fn stored_payment_methods(
+        &self,
+        shopper_reference: &ShopperReference,
+        merchant_account: &Option<&MerchantAccount>,
+    ) -> Result<Vec<StoredPaymentMethod>, Error> {
+        let merchant_account =
+            self.merchant_account_for(merchant_account, RoutingContext::default())?;
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            #[serde(default)]
+            stored_payment_methods: Vec<StoredPaymentMethod>,
+        }
+
+        let url = self.checkout_url(
+            self.api_versions.stored_payment_methods,
+            "/storedPaymentMethods",
+        );
+        let res: Response = self
+            .get(
+                &url,
+                &[
+                    ("shopperReference", shopper_reference.as_str()),
+                    ("merchantAccount", merchant_account.as_str()),
+                ],
+            )
+            .await?;
+
+        Ok(res.stored_payment_methods)
+    }
+
+    // https://docs.adyen.com/online-payments/tokenization/managing-tokens/#delete-saved-details
+    pub async fn delete_stored_payment_method(
+        &self,
+        stored_payment_method_id: &StoredPaymentMethodId,
+        shopper_reference: &ShopperReference,
+        merchant_account: &Option<&MerchantAccount>,
+    ) -> Result<(), Error> {
+        let merchant_account =
+            self.merchant_account_for(merchant_account, RoutingContext::default())?;
+
+        let url = self.checkout_url_with_segments(
+            self.api_versions.stored_payment_methods,
+            "/storedPaymentMethods",
+            &[stored_payment_method_id.as_str()],
+        )?;
+        self.delete(
+            &url,
+            &[
+                ("shopperReference", shopper_reference.as_str()),
+                ("merchantAccount", merchant_account.as_str()),
+            ],
+        )
+        .await
+    }
+}
@@ -0,0 +1,420 @@
+use crate::Amount;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Whether to attempt 3D Secure authentication when the issuer does not require it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AttemptAuthentication {
+    Always,
+    Never,
+}
+
+/// The challenge preference sent to the issuer as `threeDSRequestorChallengeInd`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChallengeIndicator {
+    #[serde(rename = "01")]
+    NoPreference,
+
+    #[serde(rename = "02")]
+    NoChallengeRequested,
+
+    #[serde(rename = "03")]
+    ChallengeRequested,
+
+    /// A challenge is required by regulation, e.g. when a card is first stored.
+    #[serde(rename = "04")]
+    ChallengeRequestedMandate,
+
+    /// No challenge, as transaction risk analysis has already been performed.
+    #[serde(rename = "05")]
+    NoChallengeRiskAnalysisPerformed,
+}
+
+/// The PSD2 SCA exemption to request, sent as `additionalData.scaExemption`.
+/// https://docs.adyen.com/online-payments/3d-secure/psd2-sca-compliance-and-implementation-guide/#exemptions
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ScaExemption {
+    LowValue,
+    TransactionRiskAnalysis,
+    TrustedBeneficiary,
+    SecureCorporate,
+}
+
+/// The merchant's assessment of the purchase, used by the issuer's risk analysis.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#request-merchantRiskIndicator
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MerchantRiskIndicator {
+    /// Whether the delivery address matches the billing address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub address_match: Option<bool>,
+
+    /// e.g. "shipToBillingAddress", "shipToVerifiedAddress", "shipToNewAddress", "shipToStore",
+    /// "digitalGoods", "goodsNotShipped" or "other".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_address_indicator: Option<String>,
+
+    /// The email address digital goods are delivered to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_email_address: Option<String>,
+
+    /// e.g. "electronicDelivery", "sameDayShipping", "overnightShipping" or
+    /// "twoOrMoreDaysShipping".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_timeframe: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub gift_card_amount: Option<Amount>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub gift_card_count: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub pre_order_date: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub pre_order_purchase: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reorder_items: Option<bool>,
+}
+
+/// What the merchant knows about the shopper's account, used by the issuer's risk analysis.
+/// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments#request-accountInfo
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    /// e.g. "notApplicable", "thisTransaction", "lessThan30Days", "from30To60Days" or
+    /// "moreThan60Days". The other `*_indicator` fields take the same values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub account_age_indicator: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub account_change_date: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub account_change_indicator: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub account_creation_date: Option<DateTime<Utc>>,
+
+    /// The number of attempts to add a card to the account in the last 24 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub add_card_attempts_day: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_address_usage_date: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub delivery_address_usage_indicator: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub password_change_date: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub password_change_indicator: Option<String>,
+
+    /// The number of transactions on the account in the last 24 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub past_transactions_day: Option<u32>,
+
+    /// The number of transactions on the account in the last year.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub past_transactions_year: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_account_age: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub payment_account_indicator: Option<String>,
+
+    /// The number of successful purchases in the last six months.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub purchases_last6_months: Option<u32>,
+
+    /// Whether suspicious activity was recorded on the account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub suspicious_activity: Option<bool>,
+}
+
+/// How a card payment is authenticated with 3D Secure 2.
+/// https://docs.adyen.com/online-payments/3d-secure/native-3ds2/
+#[derive(Debug, Clone, Default)]
+pub struct ThreeDSOptions {
+    /// Perform the authentication in the app or page (`nativeThreeDS: preferred`) rather than
+    /// with a redirect.
+    pub native: bool,
+
+    pub attempt_authentication: Option<AttemptAuthentication>,
+
+    /// Only authenticate the shopper; the payment is not authorised.
+    pub authentication_only: bool,
+
+    pub challenge_indicator: Option<ChallengeIndicator>,
+
+    pub exemption: Option<ScaExemption>,
+
+    pub merchant_risk_indicator: Option<MerchantRiskIndicator>,
+
+    pub account_info: Option<AccountInfo>,
+}
+
+impl ThreeDSOptions {
+    /// Native 3D Secure 2, always attempted.
+    pub fn preferred() -> ThreeDSOptions {
+        ThreeDSOptions {
+            native: true,
+            attempt_authentication: Some(AttemptAuthentication::Always),
+            ..ThreeDSOptions::default()
+        }
+    }
+}
+
+/// The result of a 3D Secure authentication performed outside Adyen, e.g. by a separate 3DS
+/// server, so that Adyen only authorises the payment.
+/// https://docs.adyen.com/online-payments/3d-secure/other-3ds-flows/mpidata
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MpiData {
+    /// The cryptographic proof of authentication (CAVV/AAV), base64-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub cavv: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub cavv_algorithm: Option<String>,
+
+    /// The Electronic Commerce Indicator, e.g. "05" or "02".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub eci: Option<String>,
+
+    /// The transaction identifier assigned by the directory server (3D Secure 2).
+    #[serde(rename = "dsTransID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub ds_trans_id: Option<String>,
+
+    /// The transaction identifier of 3D Secure 1 authentications.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub xid: Option<String>,
+
+    /// The 3D Secure version used, e.g. "2.2.0".
+    #[serde(rename = "threeDSVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub three_d_s_version: Option<String>,
+
+    /// The `transStatus` of the ARes (3D Secure 2) or the VERes enrollment response (3D Secure
+    /// 1), e.g. "Y", "C" or "N".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub directory_response: Option<String>,
+
+    /// The `transStatus` of the RReq (3D Secure 2) or the PARes (3D Secure 1), e.g. "Y" or "N".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub authentication_response: Option<String>,
+}
+
+/// The request fields for `ThreeDSOptions`, flattened into a `/payments` request.
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreeDSFields<'a> {
+    authentication_data: AuthenticationData,
+
+    #[serde(rename = "threeDS2RequestData")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    three_d_s2_request_data: Option<ThreeDS2RequestData<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merchant_risk_indicator: &'a Option<MerchantRiskIndicator>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_info: &'a Option<AccountInfo>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    additional_data: Option<ThreeDSAdditionalData>,
+}
+
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreeDSRequestData {
+    native_three_d_s: &'static str,
+}
+
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticationData {
+    #[serde(rename = "threeDSRequestData")]
+    three_d_s_request_data: ThreeDSRequestData,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempt_authentication: Option<AttemptAuthentication>,
+
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    authentication_only: bool,
+}
+
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreeDS2RequestData<'a> {
+    device_channel: &'a str,
+
+    #[serde(rename = "threeDSRequestorChallengeInd")]
+    three_d_s_requestor_challenge_ind: ChallengeIndicator,
+}
+
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreeDSAdditionalData {
+    sca_exemption: ScaExemption,
+}
+
+#[cfg(feature = "client")]
+impl ThreeDSOptions {
+    /// `channel` is the request's channel, e.g. "Web" or "iOS".
+    pub(crate) fn fields<'a>(&'a self, channel: &Option<&str>) -> ThreeDSFields<'a> {
+        let device_channel = match channel {
+            Some("iOS") | Some("Android") => "app",
+            _ => "browser",
+        };
+
+        ThreeDSFields {
+            authentication_data: AuthenticationData {
+                three_d_s_request_data: ThreeDSRequestData {
+                    native_three_d_s: match self.native {
+                        true => "preferred",
+                        false => "disabled",
+                    },
+                },
+                attempt_authentication: self.attempt_authentication,
+                authentication_only: self.authentication_only,
+            },
+            three_d_s2_request_data: self.challenge_indicator.map(|challenge_indicator| {
+                ThreeDS2RequestData {
+                    device_channel,
+                    three_d_s_requestor_challenge_ind: challenge_indicator,
+                }
+            }),
+            merchant_risk_indicator: &self.merchant_risk_indicator,
+            account_info: &self.account_info,
+            additional_data: self
+                .exemption
+                .map(|sca_exemption| ThreeDSAdditionalData { sca_exemption }),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_default_fields() {
+        let options = ThreeDSOptions::default();
+        assert_eq!(
+            serde_json::to_value(options.fields(&Some("Web"))).unwrap(),
+            json!({
+                "authenticationData": {
+                    "threeDSRequestData": { "nativeThreeDS": "disabled" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_all_fields() {
+        let options = ThreeDSOptions {
+            challenge_indicator: Some(ChallengeIndicator::ChallengeRequestedMandate),
+            exemption: Some(ScaExemption::LowValue),
+            authentication_only: true,
+            merchant_risk_indicator: Some(MerchantRiskIndicator {
+                delivery_email_address: Some(String::from("kari@example.com")),
+                ..MerchantRiskIndicator::default()
+            }),
+            account_info: Some(AccountInfo {
+                past_transactions_day: Some(1),
+                ..AccountInfo::default()
+            }),
+            ..ThreeDSOptions::preferred()
+        };
+
+        assert_eq!(
+            serde_json::to_value(options.fields(&Some("iOS"))).unwrap(),
+            json!({
+                "authenticationData": {
+                    "threeDSRequestData": { "nativeThreeDS": "preferred" },
+                    "attemptAuthentication": "always",
+                    "authenticationOnly": true
+                },
+                "threeDS2RequestData": {
+                    "deviceChannel": "app",
+                    "threeDSRequestorChallengeInd": "04"
+                },
+                "merchantRiskIndicator": { "deliveryEmailAddress": "kari@example.com" },
+                "accountInfo": { "pastTransactionsDay": 1 },
+                "additionalData": { "scaExemption": "lowValue" }
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_mpi_data() {
+        let mpi_data = MpiData {
+            cavv: Some(String::from("AAABBBCCC")),
+            eci: Some(String::from("05")),
+            ds_trans_id: Some(String::from("ds-1")),
+            three_d_s_version: Some(String::from("2.2.0")),
+            directory_response: Some(String::from("Y")),
+            authentication_response: Some(String::from("Y")),
+            ..MpiData::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&mpi_data).unwrap(),
+            json!({
+                "cavv": "AAABBBCCC",
+                "eci": "05",
+                "dsTransID": "ds-1",
+                "threeDSVersion": "2.2.0",
+                "directoryResponse": "Y",
+                "authenticationResponse": "Y"
+            })
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use serde_json::Value;
+use std::time::Duration;
+use tracing::{field, Span};
+
+const REDACTED: &str = "[redacted]";
+
+/// Fields whose values are never logged, wherever they appear in a body. Keys are also matched
+/// per `.`-separated part, e.g. "recurring.shopperReference" or "billingAddress.city" in
+/// `additionalData`, and any key containing "email" is redacted too.
+const REDACTED_FIELDS: &[&str] = &[
+    "x-API-key",
+    "apiKey",
+    "encryptedCardNumber",
+    "encryptedExpiryMonth",
+    "encryptedExpiryYear",
+    "encryptedSecurityCode",
+    "number",
+    "cvc",
+    "cavv",
+    "applePayToken",
+    "googlePayToken",
+    "paywithgoogle.token",
+    "shopperEmail",
+    "shopperName",
+    "billingAddress",
+    "deliveryAddress",
+    "dateOfBirth",
+    "email",
+    "telephoneNumber",
+    "shopperIP",
+    "browserInfo",
+    "holderName",
+    "cardHolderName",
+    "shopperReference",
+    "deviceFingerprint",
+    "riskData",
+    "clientData",
+    "customFields",
+    "hmacSignature",
+];
+
+fn is_redacted(key: &str) -> bool {
+    REDACTED_FIELDS.contains(&key)
+        || key.split('.').any(|part| REDACTED_FIELDS.contains(&part))
+        || key.to_lowercase().contains("email")
+}
+
+/// Replaces the values of sensitive fields, at any depth, with a placeholder.
+pub(crate) fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                if is_redacted(key) {
+                    *value = Value::String(String::from(REDACTED));
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn redacted_body(body: &Value) -> String {
+    let mut body = body.clone();
+    redact(&mut body);
+    body.to_string()
+}
+
+/// The span of one API call. The response fields are recorded by `record_response`.
+pub(crate) fn request_span(request: &reqwest::Request, log_bodies: bool) -> Span {
+    let body: Option<Value> = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .and_then(|bytes| serde_json::from_slice(bytes).ok());
+
+    let merchant_reference = body
+        .as_ref()
+        .and_then(|body| body.get("reference"))
+        .and_then(Value::as_str);
+
+    let span = tracing::info_span!(
+        "adyen.request",
+        method = %request.method(),
+        endpoint = %request.url().path(),
+        merchant_reference = field::Empty,
+        psp_reference = field::Empty,
+        result_code = field::Empty,
+        http_status = field::Empty,
+        latency_ms = field::Empty,
+        retry_count = field::Empty,
+    );
+
+    if let Some(merchant_reference) = merchant_reference {
+        span.record("merchant_reference", merchant_reference);
+    }
+
+    if log_bodies {
+        if let Some(body) = &body {
+            span.in_scope(|| tracing::debug!(body = %redacted_body(body), "adyen request"));
+        }
+    }
+
+    span
+}
+
+/// Records the outcome of the call on the current span.
+pub(crate) fn record_response(
+    status: u16,
+    text: &str,
+    latency: Duration,
+    retries: u32,
+    log_bodies: bool,
+) {
+    let span = Span::current();
+    span.record("http_status", status);
+    span.record("latency_ms", latency.as_millis() as u64);
+    span.record("retry_count", retries);
+
+    let body: Option<Value> = serde_json::from_str(text).ok();
+    if let Some(body) = &body {
+        if let Some(psp_reference) = body.get("pspReference").and_then(Value::as_str) {
+            span.record("psp_reference", psp_reference);
+        }
+        if let Some(result_code) = body.get("resultCode").and_then(Value::as_str) {
+            span.record("result_code", result_code);
+        }
+
+        if log_bodies {
+            tracing::debug!(body = %redacted_body(body), "adyen response");
+        }
+    }
+}
+
+/// Records a request that got no response on the current span.
+pub(crate) fn record_failure(error: &reqwest::Error, latency: Duration, retries: u32) {
+    let span = Span::current();
+    span.record("latency_ms", latency.as_millis() as u64);
+    span.record("retry_count", retries);
+    tracing::warn!(error = %error, "adyen request failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Address, Name, RiskData, ShopperDetails};
+    use serde_json::json;
+
+    #[test]
+    fn redacts_shopper_details() {
+        let address = Address {
+            street: String::from("Storgata"),
+            house_number_or_name: String::from("12"),
+            postal_code: String::from("0155"),
+            city: String::from("Oslo"),
+            state_or_province: None,
+            country: String::from("NO"),
+        };
+        let shopper_details = ShopperDetails {
+            shopper_name: Some(Name {
+                first_name: String::from("Kari"),
+                last_name: String::from("Nordmann"),
+            }),
+            billing_address: Some(address.clone()),
+            delivery_address: Some(address),
+            telephone_number: Some(String::from("+4712345678")),
+            date_of_birth: chrono::NaiveDate::from_ymd_opt(1990, 1, 2),
+            country_code: Some(String::from("NO")),
+            shopper_locale: Some(String::from("nb-NO")),
+            device_fingerprint: Some(String::from("fingerprint-value")),
+            risk_data: Some(RiskData {
+                client_data: Some(String::from("client-data-value")),
+                custom_fields: [(String::from("loyalty"), String::from("custom-value"))]
+                    .into_iter()
+                    .collect(),
+                fraud_offset: Some(-10),
+                profile_reference: Some(String::from("profile-value")),
+            }),
+        };
+
+        let mut body = serde_json::to_value(&shopper_details).unwrap();
+        let fields = body.as_object_mut().unwrap();
+        fields.insert(String::from("shopperReference"), json!("shopper-1"));
+        fields.insert(String::from("shopperEmail"), json!("kari@example.com"));
+        fields.insert(String::from("shopperIP"), json!("192.0.2.1"));
+        fields.insert(
+            String::from("additionalData"),
+            json!({ "cardHolderName": "Kari Nordmann" }),
+        );
+
+        let redacted = redacted_body(&body);
+        for value in [
+            "Kari",
+            "Nordmann",
+            "Storgata",
+            "0155",
+            "Oslo",
+            "+4712345678",
+            "1990-01-02",
+            "fingerprint-value",
+            "client-data-value",
+            "custom-value",
+            "profile-value",
+            "shopper-1",
+            "kari@example.com",
+            "192.0.2.1",
+        ] {
+            assert!(!redacted.contains(value), "{} in {}", value, redacted);
+        }
+
+        // Fields that are not personal data are kept.
+        assert!(redacted.contains("\"countryCode\":\"NO\""));
+        assert!(redacted.contains("nb-NO"));
+    }
+
+    #[test]
+    fn redacts_additional_data() {
+        let body = json!({
+            "additionalData": {
+                "cardSummary": "1111",
+                "cardHolderName": "Kari Nordmann",
+                "expiryDate": "3/2030",
+                "authCode": "065696",
+                "paymentMethod": "visa",
+                "recurring.recurringDetailReference": "8415995487234100",
+                "recurring.shopperReference": "shopper-1",
+                "shopperEmail": "kari@example.com",
+                "billingAddress.street": "Storgata",
+                "billingAddress.city": "Oslo",
+                "deliveryAddress.postalCode": "0155",
+                "networkTxReference": "777718270356123"
+            },
+            "amount": { "currency": "NOK", "value": 1000 },
+            "merchantReference": "order-1",
+            "paymentMethod": { "brand": "visa", "type": "scheme" },
+            "pspReference": "V4HZ4RBFJGXXGN82",
+            "resultCode": "Authorised"
+        });
+
+        let redacted = redacted_body(&body);
+        for value in [
+            "Kari Nordmann",
+            "shopper-1",
+            "kari@example.com",
+            "Storgata",
+            "Oslo",
+            "0155",
+        ] {
+            assert!(!redacted.contains(value), "{} in {}", value, redacted);
+        }
+
+        for value in [
+            "\"cardSummary\":\"1111\"",
+            "\"recurring.recurringDetailReference\":\"8415995487234100\"",
+            "\"pspReference\":\"V4HZ4RBFJGXXGN82\"",
+            "\"resultCode\":\"Authorised\"",
+        ] {
+            assert!(redacted.contains(value), "{} not in {}", value, redacted);
+        }
+    }
+
+    #[test]
+    fn redacts_delivery_email_address() {
+        let options = crate::ThreeDSOptions {
+            merchant_risk_indicator: Some(crate::MerchantRiskIndicator {
+                delivery_email_address: Some(String::from("kari@example.com")),
+                delivery_timeframe: Some(String::from("electronicDelivery")),
+                ..crate::MerchantRiskIndicator::default()
+            }),
+            ..crate::ThreeDSOptions::default()
+        };
+        let body = serde_json::to_value(options.fields(&Some("Web"))).unwrap();
+
+        let redacted = redacted_body(&body);
+        assert!(!redacted.contains("kari@example.com"), "{}", redacted);
+        assert!(redacted.contains("electronicDelivery"));
+    }
+}
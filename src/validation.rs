@@ -0,0 +1,186 @@
+// Most checks only guard requests sent by `Gateway`.
+#![cfg_attr(not(feature = "client"), allow(dead_code))]
+
+use crate::{error::Error, ShopperDetails};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// https://docs.adyen.com/api-explorer/Checkout/latest/post/payments
+const MAX_REFERENCE_LENGTH: usize = 80;
+const MIN_SHOPPER_REFERENCE_LENGTH: usize = 3;
+const MAX_SHOPPER_REFERENCE_LENGTH: usize = 256;
+const MAX_RETURN_URL_LENGTH: usize = 8000;
+const MAX_SHOPPER_EMAIL_LENGTH: usize = 300;
+
+/// A request field that does not meet Adyen's documented constraints.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    /// The name of the field in the Adyen API, e.g. "shopperReference".
+    pub field: String,
+
+    pub message: String,
+}
+
+/// Returned instead of calling Adyen when one or more request fields are invalid.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationError {
+    pub errors: Vec<FieldError>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let errors: Vec<String> = self
+            .errors
+            .iter()
+            .map(|error| format!("{} {}", error.field, error.message))
+            .collect();
+        write!(f, "invalid request: {}", errors.join(", "))
+    }
+}
+
+/// Collects the field errors of a request before it is sent.
+#[derive(Default)]
+pub(crate) struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    fn push(&mut self, field: &str, message: String) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            message,
+        });
+    }
+
+    fn length(mut self, field: &str, value: &str, min: usize, max: usize) -> Validator {
+        let length = value.chars().count();
+        if length < min {
+            match min {
+                1 => self.push(field, String::from("must not be empty")),
+                _ => self.push(field, format!("must be at least {} characters", min)),
+            }
+        } else if length > max {
+            self.push(field, format!("must be at most {} characters", max));
+        }
+        self
+    }
+
+    pub fn reference(self, reference: &str) -> Validator {
+        self.length("reference", reference, 1, MAX_REFERENCE_LENGTH)
+    }
+
+    pub fn shopper_reference(self, shopper_reference: &str) -> Validator {
+        self.length(
+            "shopperReference",
+            shopper_reference,
+            MIN_SHOPPER_REFERENCE_LENGTH,
+            MAX_SHOPPER_REFERENCE_LENGTH,
+        )
+    }
+
+    pub fn merchant_account(self, merchant_account: &str) -> Validator {
+        self.length("merchantAccount", merchant_account, 1, usize::MAX)
+    }
+
+    /// Adyen's PSP references are alphanumeric, e.g. "NC6HT9CRT65ZGN82".
+    pub fn psp_reference(mut self, psp_reference: &str) -> Validator {
+        if psp_reference.is_empty() {
+            self.push("pspReference", String::from("must not be empty"));
+        } else if !psp_reference.chars().all(|c| c.is_ascii_alphanumeric()) {
+            self.push("pspReference", String::from("must be alphanumeric"));
+        }
+        self
+    }
+
+    pub fn not_empty(self, field: &str, value: &str) -> Validator {
+        self.length(field, value, 1, usize::MAX)
+    }
+
+    /// The return URL must be an absolute URL, e.g. "https://shop.example/checkout" or
+    /// "my-app://checkout".
+    pub fn return_url(mut self, return_url: &str) -> Validator {
+        if return_url.chars().count() > MAX_RETURN_URL_LENGTH {
+            self.push(
+                "returnUrl",
+                format!("must be at most {} characters", MAX_RETURN_URL_LENGTH),
+            );
+        } else if let Err(err) = url::Url::parse(return_url) {
+            self.push("returnUrl", format!("must be an absolute URL ({})", err));
+        }
+        self
+    }
+
+    pub fn shopper_email(mut self, shopper_email: &Option<&str>) -> Validator {
+        if let Some(shopper_email) = shopper_email {
+            if !shopper_email.contains('@') {
+                self.push("shopperEmail", String::from("must be an email address"));
+            }
+            self = self.length("shopperEmail", shopper_email, 3, MAX_SHOPPER_EMAIL_LENGTH);
+        }
+        self
+    }
+
+    /// The country code must be an ISO 3166-1 alpha-2 code, e.g. "NO".
+    pub fn country_code(mut self, country_code: &Option<&str>) -> Validator {
+        if let Some(country_code) = country_code {
+            if !is_country_code(country_code) {
+                self.push(
+                    "countryCode",
+                    String::from("must be a two-letter ISO 3166-1 country code"),
+                );
+            }
+        }
+        self
+    }
+
+    /// The shopper's country and the countries of their addresses must be ISO 3166-1 alpha-2
+    /// codes.
+    pub fn shopper_details(mut self, shopper_details: &Option<&ShopperDetails>) -> Validator {
+        if let Some(shopper_details) = shopper_details {
+            self = self.country_code(&shopper_details.country_code.as_deref());
+
+            let addresses = [
+                ("billingAddress.country", &shopper_details.billing_address),
+                ("deliveryAddress.country", &shopper_details.delivery_address),
+            ];
+            for (field, address) in addresses {
+                if let Some(address) = address {
+                    if !is_country_code(&address.country) {
+                        self.push(
+                            field,
+                            String::from("must be a two-letter ISO 3166-1 country code"),
+                        );
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// Only zero-value authorisations (card verification) may have a zero amount.
+    pub fn amount(mut self, value: u64) -> Validator {
+        if value == 0 {
+            self.push("amount", String::from("must be greater than zero"));
+        }
+        self
+    }
+
+    pub fn finish(self) -> Result<(), Error> {
+        match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(Error::ValidationError(ValidationError {
+                errors: self.errors,
+            })),
+        }
+    }
+}
+
+fn is_country_code(g: &str) -> bool {
+    g.len() == 2 && g.chars().all(|c| c.is_ascii_alphabetic())
+}
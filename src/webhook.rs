@@ -1,10 +1,22 @@
-use crate::Amount;
+use crate::{
+    Amount, Error, MerchantAccount, MerchantReference, NetworkTxReference, PspReference, Secret,
+};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalData {
     pub hmac_signature: String,
+
+    /// The card scheme's reference for the payment. Store it with the card to send with later
+    /// merchant-initiated payments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub network_tx_reference: Option<NetworkTxReference>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -17,9 +29,9 @@ pub enum NotificationRequestItem {
         additional_data: AdditionalData,
         success: String,
         event_date: String,
-        merchant_account_code: String,
-        psp_reference: String,
-        merchant_reference: String,
+        merchant_account_code: MerchantAccount,
+        psp_reference: PspReference,
+        merchant_reference: MerchantReference,
         amount: Amount,
     },
 
@@ -32,9 +44,9 @@ pub enum NotificationRequestItem {
     Cancellation {
         success: String,
         event_date: String,
-        merchant_account_code: String,
-        psp_reference: String,
-        merchant_reference: String,
+        merchant_account_code: MerchantAccount,
+        psp_reference: PspReference,
+        merchant_reference: MerchantReference,
         amount: Amount,
     },
 
@@ -180,3 +192,184 @@ pub struct Webhook {
 
     pub notification_items: Vec<NotificationItem>,
 }
+
+impl Webhook {
+    /// Parses a webhook body, first checking the HMAC signature of each notification item. An
+    /// item is accepted when any of `hmac_keys` matches, so that a key can be rotated; with no
+    /// keys every item is rejected. All keys must be hexadecimal.
+    /// https://docs.adyen.com/development-resources/webhooks/verify-hmac-signatures/
+    pub fn from_signed_json(body: &str, hmac_keys: &[Secret]) -> Result<Webhook, Error> {
+        let mut keys = Vec::with_capacity(hmac_keys.len());
+        for hmac_key in hmac_keys {
+            keys.push(decode_hmac_key(hmac_key)?);
+        }
+
+        let value: Value = match serde_json::from_str(body) {
+            Ok(value) => value,
+            Err(err) => return Err(Error::SerializationError(err.to_string())),
+        };
+
+        let items = match value.get("notificationItems").and_then(Value::as_array) {
+            Some(items) => items,
+            None => {
+                return Err(Error::SerializationError(String::from(
+                    "notificationItems is missing",
+                )))
+            }
+        };
+
+        for item in items {
+            let item = match item.get("NotificationRequestItem") {
+                Some(item) => item,
+                None => return Err(Error::InvalidSignature),
+            };
+
+            if !keys.iter().any(|key| signature_matches(item, key)) {
+                return Err(Error::InvalidSignature);
+            }
+        }
+
+        match serde_json::from_value(value) {
+            Ok(webhook) => Ok(webhook),
+            Err(err) => Err(Error::SerializationError(err.to_string())),
+        }
+    }
+}
+
+/// Checks the `hmacSignature` of a single `NotificationRequestItem`, as received, against
+/// `hmac_key`, the hexadecimal key shown in the Customer Area.
+/// https://docs.adyen.com/development-resources/webhooks/verify-hmac-signatures/
+pub fn verify_hmac_signature(
+    notification_request_item: &Value,
+    hmac_key: &Secret,
+) -> Result<bool, Error> {
+    let key = decode_hmac_key(hmac_key)?;
+    Ok(signature_matches(notification_request_item, &key))
+}
+
+fn decode_hmac_key(hmac_key: &Secret) -> Result<Zeroizing<Vec<u8>>, Error> {
+    match hex::decode(hmac_key.expose()) {
+        Ok(key) => Ok(Zeroizing::new(key)),
+        Err(_) => Err(Error::ConfigurationError(String::from(
+            "hmac_keys must be hexadecimal",
+        ))),
+    }
+}
+
+fn signature_matches(notification_request_item: &Value, key: &[u8]) -> bool {
+    let signature = match notification_request_item
+        .pointer("/additionalData/hmacSignature")
+        .and_then(Value::as_str)
+        .map(base64::decode)
+    {
+        Some(Ok(signature)) => signature,
+        _ => return false,
+    };
+
+    let field = |pointer: &str| match notification_request_item.pointer(pointer) {
+        Some(Value::String(g)) => g.clone(),
+        Some(Value::Number(g)) => g.to_string(),
+        Some(Value::Bool(g)) => g.to_string(),
+        _ => String::new(),
+    };
+    let signing_string = [
+        field("/pspReference"),
+        field("/originalReference"),
+        field("/merchantAccountCode"),
+        field("/merchantReference"),
+        field("/amount/value"),
+        field("/amount/currency"),
+        field("/eventCode"),
+        field("/success"),
+    ]
+    .join(":");
+
+    // HMAC accepts keys of any length.
+    let mut mac = match <Hmac<Sha256> as Mac>::new_from_slice(key) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(signing_string.as_bytes());
+
+    // Compares in constant time.
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from Adyen's documentation.
+    const HMAC_KEY: &str = "44782DEF547AAA06C910C43932B1EB0C71FC68D9D0C057550C48EC2ACF6BA056";
+
+    fn body(hmac_signature: &str) -> String {
+        format!(
+            r#"{{
+                "live": "false",
+                "notificationItems": [{{
+                    "NotificationRequestItem": {{
+                        "additionalData": {{ "hmacSignature": "{}" }},
+                        "amount": {{ "currency": "EUR", "value": 1130 }},
+                        "eventCode": "AUTHORISATION",
+                        "eventDate": "2014-08-06T13:39:04+02:00",
+                        "merchantAccountCode": "TestMerchant",
+                        "merchantReference": "TestPayment-1407325143704",
+                        "pspReference": "7914073381342284",
+                        "success": "true"
+                    }}
+                }}]
+            }}"#,
+            hmac_signature
+        )
+    }
+
+    #[test]
+    fn accepts_a_valid_signature() {
+        let body = body("coqCmt/IZ4E3CzPvMY8zTjQVL5hYJUiBRg8UU+iCWo0=");
+        let webhook = Webhook::from_signed_json(&body, &[Secret::from(HMAC_KEY)]).unwrap();
+        assert_eq!(webhook.notification_items.len(), 1);
+
+        // Any configured key may match while keys are rotated.
+        let keys = [Secret::from("00"), Secret::from(HMAC_KEY)];
+        assert!(Webhook::from_signed_json(&body, &keys).is_ok());
+    }
+
+    #[test]
+    fn rejects_an_invalid_signature() {
+        let body = body("coqCmt/IZ4E3CzPvMY8zTjQVL5hYJUiBRg8UU+iCWo1=");
+        assert!(matches!(
+            Webhook::from_signed_json(&body, &[Secret::from(HMAC_KEY)]),
+            Err(Error::InvalidSignature)
+        ));
+
+        let body = body.replace("1130", "1131");
+        assert!(matches!(
+            Webhook::from_signed_json(&body, &[Secret::from(HMAC_KEY)]),
+            Err(Error::InvalidSignature)
+        ));
+        assert!(matches!(
+            Webhook::from_signed_json(&body, &[]),
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn rejects_a_malformed_key() {
+        let body = body("coqCmt/IZ4E3CzPvMY8zTjQVL5hYJUiBRg8UU+iCWo0=");
+        assert!(matches!(
+            Webhook::from_signed_json(&body, &[Secret::from("not hex")]),
+            Err(Error::ConfigurationError(_))
+        ));
+
+        // A malformed key is reported whatever its position, not only when it is reached.
+        for keys in [
+            [Secret::from("not hex"), Secret::from(HMAC_KEY)],
+            [Secret::from(HMAC_KEY), Secret::from("not hex")],
+        ] {
+            assert!(matches!(
+                Webhook::from_signed_json(&body, &keys),
+                Err(Error::ConfigurationError(_))
+            ));
+        }
+    }
+}